@@ -1,29 +1,72 @@
-use crate::app::app_state::AppState;
-use crate::model::wheel_mode::WheelMode;
-use crate::service::cursor_color::{get_mouse_position, get_screen_data};
-use crate::service::utils::{load_icon_data, set_dpi_awareness};
-use crate::ui::{screen::show_screen_img, wheel::show_wheel};
-use eframe::epaint::StrokeKind;
+use crate::app::app_state::{AppState, CaptureTarget};
+use crate::app::cli::GuiOptions;
+use crate::model::{
+    hotkey::{Hotkey, HotkeyEdge},
+    named_color::ColorDictionary,
+    palette::{PaletteCollection, PaletteFormat},
+    readout::Readout,
+    sample_aggregator::SampleAggregator,
+    sample_space::SampleSpace,
+    template::TemplatePreset,
+    wheel_mode::WheelMode,
+};
+use crate::service::cursor_color::sample_cursor;
+use crate::service::dictionary::{load_dictionary, load_user_dictionaries};
+use crate::service::fonts::load_cjk_font;
+use crate::service::history::{load_history, save_history};
+use crate::service::palette::{export_palette, import_palette, load_palettes, save_palettes};
+use crate::service::platform::{Platform, current_platform};
+use crate::service::screen_source::default_screen_source;
+use crate::service::template::load_user_templates;
+use crate::service::utils::load_icon_data;
+use crate::ui::{
+    color_input::{ColorInputTarget, show_color_input},
+    contrast::show_contrast,
+    copy::{show_copy_label, show_toast},
+    history::show_history,
+    named_color::show_color_name,
+    palette::{PaletteRequest, show_palettes},
+    screen::{show_screen_img, show_shape_button},
+    swatch::show_swatch,
+    template::show_template_menu,
+    wheel::show_wheel,
+};
 use egui::{
-    Button, Color32, Context, CornerRadius, Frame, Margin, PointerButton, RichText, Stroke,
-    TextureHandle, Vec2, ViewportBuilder, ViewportCommand,
+    Button, Color32, Context, CornerRadius, Frame, Key, Margin, PointerButton, RichText, Sense,
+    Stroke, TextureHandle, UiBuilder, Vec2, ViewportBuilder, ViewportCommand,
 };
-#[cfg(target_os = "windows")]
 use raw_window_handle::HasWindowHandle;
 use std::{
     error::Error,
     sync::{Arc, Mutex},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 pub struct App {
     pub state: Arc<Mutex<AppState>>,
     pub wheel_texture: Option<TextureHandle>,
     pub screen_texture: Option<TextureHandle>,
+    pub checker_texture: Option<TextureHandle>,
     pub current_screen_tex_size: usize,
+    pub current_window_width: f32,
+    pub current_window_height: f32,
+    pub dictionaries: Vec<ColorDictionary>,
+    pub dictionary_index: usize,
+    pub dictionary_message: Option<String>, // 最近一次加载词典的结果
+    pub color_input: String,
+    pub template_presets: Vec<TemplatePreset>,
+    pub toast: Option<(String, Instant)>,
+    pub history_error: Option<String>, // 读取或保存历史失败时不再写入，避免覆盖原文件
+    pub palettes: PaletteCollection,
+    pub palette_error: Option<String>, // 同上，色板文件出错时不再写入
+    pub palette_open: bool,            // 是否显示色板侧栏
 }
 
+const WINDOW_SIZE: (f32, f32) = (450.0, 300.0);
+const WINDOW_MARGIN: i8 = 10;
+const PALETTE_PANEL_WIDTH: f32 = 200.0; // 色板侧栏展开时窗口加宽
+
 // init
 impl App {
     fn new(state: Arc<Mutex<AppState>>, context: &Context) -> Self {
@@ -37,48 +80,98 @@ impl App {
                 egui::FontId::new(15.0, egui::FontFamily::Monospace),
             );
         });
+        load_cjk_font(context);
         context.send_viewport_cmd(ViewportCommand::EnableButtons {
             minimized: true,
             maximize: false,
             close: true,
         });
 
+        let history_error = match load_history() {
+            Ok(history) => {
+                state.lock().unwrap().history = history;
+                None
+            }
+            Err(e) => Some(e.to_string()),
+        };
+        let (palettes, palette_error) = match load_palettes() {
+            Ok(palettes) => (palettes, None),
+            Err(e) => (PaletteCollection::new(), Some(e.to_string())),
+        };
+
         let state_clone = state.clone();
         thread::spawn(move || {
+            let source = default_screen_source();
+            let platform = current_platform();
+            {
+                let mut s = state_clone.lock().unwrap();
+                s.explicit_pick = source.needs_explicit_pick();
+                s.global_hotkey = platform.is_hotkey_down(&Hotkey::COPY).is_some();
+            }
+            // 复制和记录快捷键按下后等到下一次采样成功再处理，保证用的是刚采样的颜色
+            let mut copy_edge = HotkeyEdge::default();
+            let mut pick_edge = HotkeyEdge::default();
+            let mut freeze_edge = HotkeyEdge::default();
+            let mut copy_pending = false;
+            let mut record_pending = false;
             loop {
                 thread::sleep(Duration::from_millis(16));
 
-                let position = match get_mouse_position() {
-                    Ok(p) => p,
+                let is_down = |hotkey| platform.is_hotkey_down(hotkey).unwrap_or(false);
+                copy_pending |= copy_edge.pressed(is_down(&Hotkey::COPY));
+                record_pending |= pick_edge.pressed(is_down(&Hotkey::PICK));
+                let freeze_pressed = freeze_edge.pressed(is_down(&Hotkey::FREEZE));
+
+                // 冻结时不采样，请求使用冻结画面中虚拟光标处的颜色
+                {
+                    let mut s = state_clone.lock().unwrap();
+                    if freeze_pressed {
+                        s.toggle_freeze();
+                    }
+                    if s.frozen.is_some() {
+                        s.apply_requests(
+                            std::mem::take(&mut copy_pending),
+                            std::mem::take(&mut record_pending),
+                        );
+                        continue;
+                    }
+                }
+
+                let pick_requested =
+                    std::mem::take(&mut state_clone.lock().unwrap().pick_requested);
+                // 通过门户取色成功后记入历史
+                if pick_requested && source.pick().is_ok() {
+                    record_pending = true;
+                }
+
+                let settings = state_clone.lock().unwrap().sample_settings();
+                let (position, screen_data) = match sample_cursor(&*source, &settings) {
+                    Ok(v) => v,
                     Err(_) => {
                         continue;
                     }
                 };
-
-                let screen_tex_size = state_clone.lock().unwrap().screen_tex_size;
-                let screen_sample_size = state_clone.lock().unwrap().screen_sample_size;
-                let screen_data =
-                    match get_screen_data(position, screen_tex_size, screen_sample_size) {
-                        Ok(v) => v,
-                        Err(_) => {
-                            continue;
-                        }
-                    };
                 let color = screen_data.cursor_pixel_color;
                 let colors = screen_data.screen_pixel_colors;
 
-                let _ = {
+                {
                     let mut s = state_clone.lock().unwrap();
+                    // 采样期间可能刚被冻结，不覆盖冻结的画面
+                    if s.frozen.is_some() {
+                        continue;
+                    }
                     if position != s.position || color != s.color {
                         s.screen_colors = colors;
+                        s.screen_bounds = screen_data.screen_bounds;
+                        s.sample_stats = screen_data.sample_stats;
                         s.position = position;
                         s.color = color;
-
-                        true
-                    } else {
-                        false
                     }
-                };
+                    s.apply_requests(
+                        std::mem::take(&mut copy_pending),
+                        std::mem::take(&mut record_pending),
+                    );
+                }
             }
         });
 
@@ -86,13 +179,34 @@ impl App {
             state,
             wheel_texture: None,
             screen_texture: None,
+            checker_texture: None,
             current_screen_tex_size: 0,
+            current_window_width: WINDOW_SIZE.0,
+            current_window_height: WINDOW_SIZE.1,
+            dictionaries: ColorDictionary::builtin()
+                .into_iter()
+                .chain(load_user_dictionaries())
+                .collect(),
+            dictionary_index: 0,
+            dictionary_message: None,
+            color_input: String::new(),
+            template_presets: TemplatePreset::builtin()
+                .into_iter()
+                .chain(load_user_templates())
+                .collect(),
+            toast: None,
+            history_error,
+            palettes,
+            palette_error,
+            palette_open: false,
         }
     }
 
-    pub fn run() -> Result<(), Box<dyn Error>> {
-        set_dpi_awareness()?;
+    pub fn run(options: GuiOptions) -> Result<(), Box<dyn Error>> {
+        let platform = current_platform();
+        platform.set_dpi_awareness()?;
         let state = AppState::new();
+        options.apply(&mut state.lock().unwrap());
         eframe::run_native(
             "Colorose",
             eframe::NativeOptions {
@@ -100,7 +214,7 @@ impl App {
                     .with_always_on_top()
                     .with_has_shadow(true)
                     .with_decorations(true)
-                    .with_inner_size((450.0, 300.0))
+                    .with_inner_size(WINDOW_SIZE)
                     .with_icon(load_icon_data("resources/app-icon.png").unwrap())
                     .with_taskbar(true)
                     .with_drag_and_drop(true)
@@ -113,17 +227,10 @@ impl App {
                 ..Default::default()
             },
             Box::new(|cc| {
-                #[cfg(target_os = "windows")]
                 if let Ok(handle) = cc.window_handle() {
-                    use raw_window_handle::RawWindowHandle;
-
-                    if let RawWindowHandle::Win32(handle) = handle.as_raw() {
-                        use windows::Win32::Foundation::HWND;
-
-                        use crate::service::utils::enable_acrylic_effect;
-                        let hwnd = HWND(handle.hwnd.get() as *mut _);
-                        enable_acrylic_effect(hwnd).expect("Failed to enable acrylic effect");
-                    }
+                    platform
+                        .enable_backdrop(handle.as_raw())
+                        .expect("Failed to enable backdrop effect");
                 }
 
                 Ok(Box::new(App::new(state.clone(), &cc.egui_ctx.clone())))
@@ -138,22 +245,132 @@ impl eframe::App for App {
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
         let mut state = self.state.lock().unwrap();
 
-        let color = Color32::from_rgb(state.color.r, state.color.g, state.color.b);
-        let color_revert = Color32::from_rgb(
-            state.color.revert().r,
-            state.color.revert().g,
-            state.color.revert().b,
-        );
-        let hsv = state.color.to_hsv();
-        let hsl = state.color.to_hsl();
+        let color = state.color.to_color32();
+        let color_revert = state.color.revert().to_color32();
 
         let fg_color = Color32::from_rgb(219, 214, 201);
         // let bg_color = Color32::from_rgb(43, 43, 43);
 
-        egui::CentralPanel::default()
+        // 拖入 .json/.csv 文件加载为命名颜色词典，拖入色板文件导入为色板
+        let dropped_files = ctx.input(|input| input.raw.dropped_files.clone());
+        for path in dropped_files.iter().filter_map(|file| file.path.as_ref()) {
+            let extension = path.extension().unwrap_or_default().to_string_lossy();
+            if PaletteFormat::from_extension(&extension).is_some() {
+                let message = match import_palette(path) {
+                    Ok(palette) => {
+                        let message = format!("imported {}", palette.name);
+                        self.palettes.push(palette);
+                        self.palette_open = true;
+                        message
+                    }
+                    Err(e) => e.to_string(),
+                };
+                self.toast = Some((message, Instant::now()));
+                continue;
+            }
+            match load_dictionary(path) {
+                Ok(dictionary) => {
+                    self.dictionary_message = Some(format!("loaded {}", dictionary.name));
+                    self.dictionaries.push(dictionary);
+                    self.dictionary_index = self.dictionaries.len() - 1;
+                }
+                Err(e) => self.dictionary_message = Some(e.to_string()),
+            }
+        }
+
+        // 窗口有焦点时，按 F/B 让取色线程把鼠标处的颜色作为对比度的前景/背景
+        // 输入框正在编辑时不处理
+        let typing = ctx.wants_keyboard_input();
+        ctx.input(|input| {
+            if typing {
+                return;
+            }
+            if input.key_pressed(Key::F) {
+                state.capture_request = Some(CaptureTarget::Foreground);
+            }
+            if input.key_pressed(Key::B) {
+                state.capture_request = Some(CaptureTarget::Background);
+            }
+            // 平台无法全局读取快捷键时，只在窗口有焦点时响应
+            if !state.global_hotkey && Hotkey::COPY.pressed(input) {
+                state.clipboard = Some(state.readout_text(state.primary_readout));
+            }
+            if !state.global_hotkey && Hotkey::PICK.pressed(input) {
+                state.record_pick();
+            }
+            if !state.global_hotkey && Hotkey::FREEZE.pressed(input) {
+                state.toggle_freeze();
+            }
+            // 冻结时方向键逐像素移动虚拟光标
+            if state.frozen.is_some() {
+                for (key, dx, dy) in [
+                    (Key::ArrowLeft, -1, 0),
+                    (Key::ArrowRight, 1, 0),
+                    (Key::ArrowUp, 0, -1),
+                    (Key::ArrowDown, 0, 1),
+                ] {
+                    if input.key_pressed(key) {
+                        state.nudge(dx, dy);
+                    }
+                }
+            }
+        });
+        state.refresh_frozen();
+
+        let mut copied: Option<String> = None;
+
+        // 色板侧栏在中心面板之前显示，占用窗口右侧
+        if self.palette_open {
+            egui::SidePanel::right("palettes")
+                .resizable(false)
+                .exact_width(PALETTE_PANEL_WIDTH)
+                .frame(Frame {
+                    fill: Color32::TRANSPARENT,
+                    inner_margin: Margin::same(WINDOW_MARGIN),
+                    ..Default::default()
+                })
+                .show(ctx, |ui| {
+                    match show_palettes(
+                        ui,
+                        &mut self.checker_texture,
+                        &mut self.palettes,
+                        &state.color,
+                        self.palette_error.as_deref(),
+                        fg_color,
+                    ) {
+                        Some(PaletteRequest::Copy(text)) => copied = Some(text),
+                        Some(PaletteRequest::Export(index, format)) => {
+                            let palette = &self.palettes.palettes()[index];
+                            let message = match export_palette(palette, format) {
+                                Ok(path) => format!("exported {}", path.display()),
+                                Err(e) => e.to_string(),
+                            };
+                            self.toast = Some((message, Instant::now()));
+                        }
+                        Some(PaletteRequest::CopyCode(index, format)) => {
+                            ctx.copy_text(format.export(&self.palettes.palettes()[index]));
+                            self.toast =
+                                Some((format!("copied {}", format.name()), Instant::now()));
+                        }
+                        Some(PaletteRequest::CopyTerminal(index, format)) => {
+                            let message = match format.export(&self.palettes.palettes()[index]) {
+                                Ok(text) => {
+                                    ctx.copy_text(text);
+                                    format!("copied {}", format.name())
+                                }
+                                Err(e) => e.to_string(),
+                            };
+                            self.toast = Some((message, Instant::now()));
+                        }
+                        None => {}
+                    }
+                });
+        }
+
+        let content_height = egui::CentralPanel::default()
             .frame(Frame {
                 fill: Color32::TRANSPARENT,
-                inner_margin: Margin::same(10),
+                inner_margin: Margin::same(WINDOW_MARGIN),
                 // corner_radius: CornerRadius::same(5),
                 // stroke: Stroke::new(2.0, fg_color),
                 ..Default::default()
@@ -169,19 +386,28 @@ impl eframe::App for App {
                             .show(ui, |ui| {
                                 ui.vertical(|ui| {
                                     let desired_size = egui::vec2(50.0, 50.0); // 正方形大小
-                                    let (rect, _) =
-                                        ui.allocate_exact_size(desired_size, egui::Sense::hover());
-
                                     let rounding = CornerRadius::same(10); // 圆角半径
                                     let stroke = Stroke::new(2.0, fg_color);
 
-                                    ui.painter().rect(
-                                        rect,
-                                        rounding,
+                                    // 点击颜色块确认取色，记入历史
+                                    let swatch_response = show_swatch(
+                                        ui,
+                                        &mut self.checker_texture,
                                         color,
+                                        desired_size,
+                                        rounding,
                                         stroke,
-                                        StrokeKind::Middle,
-                                    );
+                                    )
+                                    .interact(Sense::click())
+                                    .on_hover_text(format!(
+                                        "{}\n{}\nclick or {} to add to history",
+                                        state.color.to_hex_alpha(),
+                                        state.color.to_rgba_string(),
+                                        Hotkey::PICK
+                                    ));
+                                    if swatch_response.clicked() {
+                                        state.record_pick();
+                                    }
                                 });
                                 ui.vertical(|ui| {
                                     Frame {
@@ -196,19 +422,116 @@ impl eframe::App for App {
                                     }
                                     .show(ui, |ui| {
                                         ui.set_width(210.0);
-                                        ui.label(
-                                            RichText::new(state.position).color(fg_color).strong(),
-                                        );
-                                        ui.label(
-                                            RichText::new(state.color).color(fg_color).strong(),
-                                        );
-                                        ui.label(
-                                            RichText::new(state.color.to_hex())
-                                                .color(fg_color)
-                                                .strong(),
+                                        // 背景先注册点击，读数标签在其上面，点击复制，右键打开菜单
+                                        ui.scope_builder(
+                                            UiBuilder::new().sense(Sense::click()),
+                                            |ui| {
+                                                let position = state.position.to_string();
+                                                if show_copy_label(ui, &position, fg_color) {
+                                                    copied = Some(position);
+                                                }
+                                                let rgb = state.readout_text(Readout::RGB);
+                                                if show_copy_label(ui, &rgb, fg_color) {
+                                                    copied = Some(rgb);
+                                                }
+                                                ui.horizontal(|ui| {
+                                                    let hex = state.readout_text(Readout::Hex);
+                                                    if show_copy_label(ui, &hex, fg_color) {
+                                                        copied = Some(hex);
+                                                    }
+                                                    show_color_name(
+                                                        ui,
+                                                        &self.dictionaries,
+                                                        &mut self.dictionary_index,
+                                                        self.dictionary_message.as_deref(),
+                                                        &state.color,
+                                                        fg_color,
+                                                    );
+                                                });
+                                                for readout in [Readout::HSV, Readout::HSL]
+                                                    .into_iter()
+                                                    .chain(state.readouts.iter().copied())
+                                                {
+                                                    let text = state.readout_text(readout);
+                                                    if show_copy_label(ui, &text, fg_color) {
+                                                        copied = Some(text);
+                                                    }
+                                                }
+                                                if let Some(reference) = state.reference {
+                                                    ui.horizontal(|ui| {
+                                                        show_swatch(
+                                                            ui,
+                                                            &mut self.checker_texture,
+                                                            reference.to_color32(),
+                                                            egui::vec2(15.0, 15.0),
+                                                            CornerRadius::same(3),
+                                                            Stroke::new(1.0, fg_color),
+                                                        )
+                                                        .on_hover_text(reference.to_hex_auto());
+                                                        let delta_e = state
+                                                            .delta_e
+                                                            .between(&reference, &state.color);
+                                                        ui.label(
+                                                            RichText::new(format!(
+                                                                "{}:{:.4}",
+                                                                state.delta_e.name(),
+                                                                delta_e
+                                                            ))
+                                                            .color(fg_color)
+                                                            .strong(),
+                                                        );
+                                                    });
+                                                }
+                                            },
+                                        )
+                                        .response
+                                        .context_menu(
+                                            |ui| {
+                                                // 右键选择额外显示的读数
+                                                for readout in Readout::OPTIONAL {
+                                                    let mut shown =
+                                                        state.readouts.contains(&readout);
+                                                    if ui
+                                                        .checkbox(&mut shown, readout.name())
+                                                        .changed()
+                                                    {
+                                                        if shown {
+                                                            state.readouts.push(readout);
+                                                        } else {
+                                                            state
+                                                                .readouts
+                                                                .retain(|r| *r != readout);
+                                                        }
+                                                    }
+                                                }
+                                                ui.separator();
+                                                let rows: Vec<Readout> = Readout::FIXED
+                                                    .into_iter()
+                                                    .chain(state.readouts.iter().copied())
+                                                    .collect();
+                                                let color = state.color;
+                                                show_template_menu(
+                                                    ui,
+                                                    &rows,
+                                                    &self.template_presets,
+                                                    &mut state.templates,
+                                                    &color,
+                                                );
+                                                // 复制快捷键复制的读数行
+                                                ui.menu_button(
+                                                    format!("{} copies", Hotkey::COPY),
+                                                    |ui| {
+                                                        for readout in rows {
+                                                            ui.radio_value(
+                                                                &mut state.primary_readout,
+                                                                readout,
+                                                                readout.name(),
+                                                            );
+                                                        }
+                                                    },
+                                                );
+                                            },
                                         );
-                                        ui.label(RichText::new(hsv).color(fg_color).strong());
-                                        ui.label(RichText::new(hsl).color(fg_color).strong());
                                     });
                                 });
                                 ui.vertical(|ui| {
@@ -234,25 +557,63 @@ impl eframe::App for App {
                                         .show(ui, |ui| {
                                             ui.set_width(120.0);
                                             ui.set_height(25.0);
-                                            let sample_size = state.screen_sample_size;
-                                            let sample_size_btn =
-                                                Button::new(format!("sample: {:2}", sample_size))
-                                                    .min_size(Vec2::new(100.0, 20.0));
-                                            let sample_size_btn_response = ui.add(sample_size_btn);
-                                            if sample_size_btn_response
-                                                .clicked_by(PointerButton::Secondary)
-                                            {
-                                                if sample_size < state.screen_tex_size {
-                                                    state.screen_sample_size = sample_size + 2;
+                                            ui.horizontal(|ui| {
+                                                let sample_size = state.screen_sample_size;
+                                                let sample_size_btn = Button::new(format!(
+                                                    "sample: {:2}",
+                                                    sample_size
+                                                ))
+                                                .min_size(Vec2::new(95.0, 20.0));
+                                                let sample_size_btn_response =
+                                                    ui.add(sample_size_btn).on_hover_text(format!(
+                                                        "{} ({})\n{}",
+                                                        state.sample_aggregator.name(),
+                                                        state.sample_space.name(),
+                                                        state.sample_stats
+                                                    ));
+                                                #[allow(clippy::collapsible_if)]
+                                                if sample_size_btn_response
+                                                    .clicked_by(PointerButton::Secondary)
+                                                {
+                                                    if sample_size < state.screen_tex_size {
+                                                        state.screen_sample_size = sample_size + 2;
+                                                    }
                                                 }
-                                            }
-                                            if sample_size_btn_response
-                                                .clicked_by(PointerButton::Primary)
-                                            {
-                                                if sample_size > 1 {
-                                                    state.screen_sample_size = sample_size - 2;
+                                                #[allow(clippy::collapsible_if)]
+                                                if sample_size_btn_response
+                                                    .clicked_by(PointerButton::Primary)
+                                                {
+                                                    if sample_size > 1 {
+                                                        state.screen_sample_size = sample_size - 2;
+                                                    }
                                                 }
-                                            }
+                                                // 点击切换采样形状，右键选择合成方式
+                                                let shape_btn_response = show_shape_button(
+                                                    ui,
+                                                    state.sample_shape,
+                                                    fg_color,
+                                                );
+                                                if shape_btn_response.clicked() {
+                                                    state.sample_shape = state.sample_shape.next();
+                                                }
+                                                shape_btn_response.context_menu(|ui| {
+                                                    for aggregator in SampleAggregator::ALL {
+                                                        ui.radio_value(
+                                                            &mut state.sample_aggregator,
+                                                            aggregator,
+                                                            aggregator.name(),
+                                                        );
+                                                    }
+                                                    ui.separator();
+                                                    for space in SampleSpace::ALL {
+                                                        ui.radio_value(
+                                                            &mut state.sample_space,
+                                                            space,
+                                                            space.name(),
+                                                        );
+                                                    }
+                                                });
+                                            });
                                         });
                                         Frame {
                                             inner_margin: Margin {
@@ -274,6 +635,7 @@ impl eframe::App for App {
                                             .min_size(Vec2::new(100.0, 20.0));
                                             let screen_tex_size_btn_response =
                                                 ui.add(screen_tex_size_btn);
+                                            #[allow(clippy::collapsible_if)]
                                             if screen_tex_size_btn_response
                                                 .clicked_by(PointerButton::Secondary)
                                             {
@@ -281,6 +643,7 @@ impl eframe::App for App {
                                                     state.screen_tex_size = screen_tex_size + 2;
                                                 }
                                             }
+                                            #[allow(clippy::collapsible_if)]
                                             if screen_tex_size_btn_response
                                                 .clicked_by(PointerButton::Primary)
                                             {
@@ -319,6 +682,122 @@ impl eframe::App for App {
                                                 };
                                             }
                                         });
+                                        Frame {
+                                            inner_margin: Margin {
+                                                left: 5,
+                                                right: 5,
+                                                top: 2,
+                                                bottom: 2,
+                                            },
+                                            ..Default::default()
+                                        }
+                                        .show(ui, |ui| {
+                                            ui.set_width(120.0);
+                                            ui.set_height(25.0);
+                                            let delta_e = state.delta_e;
+                                            let lock_text = if state.reference.is_some() {
+                                                "unlock"
+                                            } else {
+                                                "lock"
+                                            };
+                                            let delta_e_btn = Button::new(format!(
+                                                "{}: {}",
+                                                delta_e.name(),
+                                                lock_text
+                                            ))
+                                            .min_size(Vec2::new(100.0, 20.0));
+                                            let delta_e_btn_response = ui.add(delta_e_btn);
+                                            // 左键锁定/解锁参考色，右键切换色差公式
+                                            if delta_e_btn_response
+                                                .clicked_by(PointerButton::Primary)
+                                            {
+                                                state.reference = match state.reference {
+                                                    Some(_) => None,
+                                                    None => Some(state.color),
+                                                };
+                                            }
+                                            if delta_e_btn_response
+                                                .clicked_by(PointerButton::Secondary)
+                                            {
+                                                state.delta_e = delta_e.next();
+                                            }
+                                        });
+                                        Frame {
+                                            inner_margin: Margin {
+                                                left: 5,
+                                                right: 5,
+                                                top: 2,
+                                                bottom: 2,
+                                            },
+                                            ..Default::default()
+                                        }
+                                        .show(ui, |ui| {
+                                            ui.set_width(120.0);
+                                            ui.set_height(25.0);
+                                            let freeze_text = if state.frozen.is_some() {
+                                                "unfreeze"
+                                            } else {
+                                                "freeze"
+                                            };
+                                            let freeze_btn = Button::new(freeze_text)
+                                                .min_size(Vec2::new(100.0, 20.0));
+                                            if ui
+                                                .add(freeze_btn)
+                                                .on_hover_text(format!(
+                                                    "{}, arrow keys move one pixel while frozen",
+                                                    Hotkey::FREEZE
+                                                ))
+                                                .clicked()
+                                            {
+                                                state.toggle_freeze();
+                                            }
+                                        });
+                                        Frame {
+                                            inner_margin: Margin {
+                                                left: 5,
+                                                right: 5,
+                                                top: 2,
+                                                bottom: 2,
+                                            },
+                                            ..Default::default()
+                                        }
+                                        .show(ui, |ui| {
+                                            ui.set_width(120.0);
+                                            ui.set_height(25.0);
+                                            let palette_text = if self.palette_open {
+                                                "palettes <<"
+                                            } else {
+                                                "palettes >>"
+                                            };
+                                            let palette_btn = Button::new(palette_text)
+                                                .min_size(Vec2::new(100.0, 20.0));
+                                            if ui.add(palette_btn).clicked() {
+                                                self.palette_open = !self.palette_open;
+                                            }
+                                        });
+                                        if state.explicit_pick {
+                                            Frame {
+                                                inner_margin: Margin {
+                                                    left: 5,
+                                                    right: 5,
+                                                    top: 2,
+                                                    bottom: 2,
+                                                },
+                                                ..Default::default()
+                                            }
+                                            .show(
+                                                ui,
+                                                |ui| {
+                                                    ui.set_width(120.0);
+                                                    ui.set_height(25.0);
+                                                    let pick_btn = Button::new("pick")
+                                                        .min_size(Vec2::new(100.0, 20.0));
+                                                    if ui.add(pick_btn).clicked() {
+                                                        state.pick_requested = true;
+                                                    }
+                                                },
+                                            );
+                                        }
                                     });
                                 });
                             });
@@ -334,23 +813,122 @@ impl eframe::App for App {
                                 &state.wheel_mode,
                             );
 
+                            // 冻结时显示保存的画面和虚拟光标处的采样范围
+                            let sample_shape = state.sample_shape;
+                            let (tex_size, cursor_offset) = match &state.frozen {
+                                Some(frozen) => (frozen.tex_size, frozen.offset),
+                                None => (state.screen_tex_size, (0, 0)),
+                            };
                             show_screen_img(
                                 ui,
                                 &mut self.screen_texture,
                                 160.0,
-                                state.screen_tex_size,
+                                tex_size,
                                 state.screen_colors.iter().map(|c| c.to_color32()).collect(),
                                 color_revert,
                                 &mut state.screen_sample_size,
+                                sample_shape,
+                                cursor_offset,
                                 &mut self.current_screen_tex_size,
                             );
                         });
+                        let hint =
+                            format!("click the swatch or press {} to keep a color", Hotkey::PICK);
+                        if let Some(text) = show_history(
+                            ui,
+                            &mut self.checker_texture,
+                            &mut state.history,
+                            self.history_error.as_deref(),
+                            &hint,
+                            fg_color,
+                        ) {
+                            copied = Some(text);
+                        }
+                        egui::CollapsingHeader::new(
+                            RichText::new("contrast").color(fg_color).strong(),
+                        )
+                        .show(ui, |ui| {
+                            let AppState {
+                                contrast_fg,
+                                contrast_bg,
+                                ..
+                            } = &mut *state;
+                            show_contrast(
+                                ui,
+                                &mut self.checker_texture,
+                                contrast_fg,
+                                contrast_bg,
+                                fg_color,
+                            );
+                        });
+                        egui::CollapsingHeader::new(
+                            RichText::new("input").color(fg_color).strong(),
+                        )
+                        .show(ui, |ui| {
+                            if let Some((color, target)) = show_color_input(
+                                ui,
+                                &mut self.checker_texture,
+                                &mut self.color_input,
+                                fg_color,
+                            ) {
+                                match target {
+                                    ColorInputTarget::Reference => state.reference = Some(color),
+                                    ColorInputTarget::Foreground => state.contrast_fg = color,
+                                    ColorInputTarget::Background => state.contrast_bg = color,
+                                }
+                            }
+                        });
                     });
                     // ui.vertical(|ui| {
                     // let _ = ui.button(state.color.to_hex());
                     // });
-                });
-            });
+                })
+                .response
+                .rect
+                .height()
+            })
+            .inner;
+
+        // 点击读数或按下复制快捷键
+        if let Some(text) = copied.or_else(|| state.clipboard.take()) {
+            self.toast = Some((format!("copied {}", text), Instant::now()));
+            ctx.copy_text(text);
+        }
+        show_toast(ctx, &mut self.toast, fg_color);
+
+        // 历史有变化时写入文件
+        if state.history.take_dirty()
+            && self.history_error.is_none()
+            && let Err(e) = save_history(&state.history)
+        {
+            self.history_error = Some(e.to_string());
+        }
+        if self.palettes.take_dirty()
+            && self.palette_error.is_none()
+            && let Err(e) = save_palettes(&self.palettes)
+        {
+            self.palette_error = Some(e.to_string());
+        }
+
+        // 窗口不可调整大小，读数行增减时按内容调整高度，展开色板时加宽
+        let window_width = if self.palette_open {
+            WINDOW_SIZE.0 + PALETTE_PANEL_WIDTH
+        } else {
+            WINDOW_SIZE.0
+        };
+        let window_height = WINDOW_SIZE
+            .1
+            .max(content_height + 2.0 * WINDOW_MARGIN as f32);
+        if (window_height - self.current_window_height).abs() > 0.5
+            || (window_width - self.current_window_width).abs() > 0.5
+        {
+            self.current_window_width = window_width;
+            self.current_window_height = window_height;
+            ctx.send_viewport_cmd(ViewportCommand::InnerSize(Vec2::new(
+                window_width,
+                window_height,
+            )));
+        }
 
         ctx.request_repaint_after(Duration::from_millis(34));
     }
@@ -1,16 +1,79 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
 
-use crate::model::{color::Color, position::Position, wheel_mode::WheelMode};
+use crate::{
+    model::{
+        color::{Color, difference::DeltaE},
+        history::{History, HistoryEntry, now},
+        position::Position,
+        readout::Readout,
+        sample_aggregator::{SampleAggregator, SampleStats},
+        sample_shape::SampleShape,
+        sample_space::SampleSpace,
+        template::Template,
+        wheel_mode::WheelMode,
+    },
+    service::cursor_color::{GridBounds, SampleSettings, sample_region},
+};
 
-#[derive(Debug, Default, PartialEq, Eq)]
+// 冻结时保存的放大镜画面，方向键在其中移动虚拟光标
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrozenScreen {
+    pub center: Position, // 画面中心对应的屏幕坐标
+    pub colors: Vec<Color>,
+    pub tex_size: usize,
+    pub bounds: GridBounds, // 画面中位于显示器内的部分
+    pub offset: (i32, i32), // 虚拟光标相对中心的偏移
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureTarget {
+    Foreground,
+    Background,
+}
+
+#[derive(Debug, Default, PartialEq)]
 pub struct AppState {
     pub position: Position,
     pub color: Color,
     pub screen_colors: Vec<Color>,
-    
+    pub screen_bounds: GridBounds,
+
     pub screen_tex_size: usize,
     pub screen_sample_size: usize,
+    pub sample_shape: SampleShape,
+    pub sample_aggregator: SampleAggregator,
+    pub sample_space: SampleSpace, // 合成采样像素的颜色空间
+    pub sample_stats: SampleStats, // 最近一次采样范围的统计
     pub wheel_mode: WheelMode,
+    pub readouts: Vec<Readout>, // HSV/HSL 下额外显示的读数行
+    pub templates: BTreeMap<Readout, Template>, // 读数行选用的输出模板，没有则用默认格式
+
+    // 复制快捷键复制的读数行，取色线程按下快捷键时写入 clipboard，由界面线程复制
+    pub primary_readout: Readout,
+    pub clipboard: Option<String>,
+    pub global_hotkey: bool, // 平台能否在窗口没有焦点时读取快捷键
+
+    // 锁定的参考色，和当前颜色比较色差
+    pub reference: Option<Color>,
+    pub delta_e: DeltaE,
+
+    // 对比度面板的前景和背景，由取色线程在下一次采样时写入
+    pub contrast_fg: Color,
+    pub contrast_bg: Color,
+    pub capture_request: Option<CaptureTarget>,
+
+    // 确认的取色，点击颜色块或按快捷键时记录
+    pub history: History,
+
+    // 冻结时取色线程暂停，颜色由 frozen 中的画面计算
+    pub frozen: Option<FrozenScreen>,
+
+    // Wayland 下需要点击 pick 按钮取色
+    pub explicit_pick: bool,
+    pub pick_requested: bool,
 }
 
 impl AppState {
@@ -19,7 +82,268 @@ impl AppState {
             screen_tex_size: 21,
             screen_sample_size: 1,
             wheel_mode: WheelMode::HSV,
+            contrast_fg: Color::new(0, 0, 0),
+            contrast_bg: Color::new(255, 255, 255),
             ..Default::default()
         }))
     }
+
+    pub fn readout_text(&self, readout: Readout) -> String {
+        match self.templates.get(&readout) {
+            Some(template) => template.render(&self.color),
+            None => readout.format(&self.color),
+        }
+    }
+
+    // 取色线程更新颜色后处理对比度取色、复制和记录请求
+    pub fn apply_requests(&mut self, copy: bool, record: bool) {
+        if let Some(target) = self.capture_request.take() {
+            match target {
+                CaptureTarget::Foreground => self.contrast_fg = self.color,
+                CaptureTarget::Background => self.contrast_bg = self.color,
+            }
+        }
+        if copy {
+            self.clipboard = Some(self.readout_text(self.primary_readout));
+        }
+        if record {
+            self.record_pick();
+        }
+    }
+
+    // 把当前颜色和采样设置记入历史
+    pub fn record_pick(&mut self) {
+        self.history.push(HistoryEntry {
+            color: self.color,
+            timestamp: now(),
+            position: self.position,
+            sample_size: self.screen_sample_size,
+            sample_shape: self.sample_shape,
+            sample_aggregator: self.sample_aggregator,
+            sample_space: self.sample_space,
+        });
+    }
+
+    pub fn sample_settings(&self) -> SampleSettings {
+        SampleSettings {
+            tex_size: self.screen_tex_size,
+            sample_size: self.screen_sample_size,
+            shape: self.sample_shape,
+            aggregator: self.sample_aggregator,
+            space: self.sample_space,
+        }
+    }
+
+    // 冻结时保存当前画面，再次调用解除冻结
+    pub fn toggle_freeze(&mut self) {
+        if self.frozen.take().is_some() {
+            return;
+        }
+        let tex_size = self.screen_colors.len().isqrt();
+        if tex_size == 0 || tex_size * tex_size != self.screen_colors.len() {
+            return;
+        }
+        self.frozen = Some(FrozenScreen {
+            center: self.position,
+            colors: self.screen_colors.clone(),
+            tex_size,
+            bounds: self.screen_bounds,
+            offset: (0, 0),
+        });
+    }
+
+    // 在冻结的画面中移动虚拟光标，不超出画面和显示器
+    // 画面完全不在显示器内时 bounds 为空，光标不动
+    pub fn nudge(&mut self, dx: i32, dy: i32) {
+        if let Some(frozen) = &mut self.frozen {
+            let size = frozen.tex_size as i32;
+            let half = size / 2;
+            let (min, max) = (frozen.bounds.min, frozen.bounds.max);
+            let (min, max) = (
+                (min.0.max(0), min.1.max(0)),
+                (max.0.min(size), max.1.min(size)),
+            );
+            if min.0 >= max.0 || min.1 >= max.1 {
+                return;
+            }
+            frozen.offset.0 = (frozen.offset.0 + dx).clamp(min.0 - half, max.0 - 1 - half);
+            frozen.offset.1 = (frozen.offset.1 + dy).clamp(min.1 - half, max.1 - 1 - half);
+        }
+    }
+
+    // 按虚拟光标和当前的采样设置重新计算冻结画面中的颜色
+    pub fn refresh_frozen(&mut self) {
+        let Some(frozen) = &self.frozen else {
+            return;
+        };
+        let half = (frozen.tex_size / 2) as i32;
+        let settings = SampleSettings {
+            tex_size: frozen.tex_size,
+            ..self.sample_settings()
+        };
+        let (color, stats) = sample_region(
+            &frozen.colors,
+            frozen.tex_size,
+            frozen.bounds,
+            (half + frozen.offset.0, half + frozen.offset.1),
+            &settings,
+        );
+        self.position = Position {
+            x: frozen.center.x + frozen.offset.0,
+            y: frozen.center.y + frozen.offset.1,
+        };
+        self.color = color;
+        self.sample_stats = stats;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::app_state::AppState,
+        model::{color::Color, position::Position, sample_aggregator::SampleAggregator},
+        service::cursor_color::GridBounds,
+    };
+
+    // 5×5 的画面，像素 (x, y) 的红色为 x * 10 + y，中心在屏幕 (100, 200)
+    fn screen_state(bounds: GridBounds) -> AppState {
+        AppState {
+            position: Position { x: 100, y: 200 },
+            color: Color::new(22, 0, 0),
+            screen_colors: (0..25)
+                .map(|i| Color::new((i % 5 * 10 + i / 5) as u8, 0, 0))
+                .collect(),
+            screen_bounds: bounds,
+            screen_tex_size: 5,
+            screen_sample_size: 1,
+            ..Default::default()
+        }
+    }
+
+    fn offset(state: &AppState) -> (i32, i32) {
+        state.frozen.as_ref().unwrap().offset
+    }
+
+    #[test]
+    fn freeze_and_unfreeze() {
+        let mut state = screen_state(GridBounds::full(5, 5));
+        state.toggle_freeze();
+        let frozen = state.frozen.clone().unwrap();
+        assert_eq!(frozen.center, Position { x: 100, y: 200 });
+        assert_eq!(frozen.tex_size, 5);
+        assert_eq!(frozen.colors, state.screen_colors);
+        assert_eq!(frozen.bounds, GridBounds::full(5, 5));
+        assert_eq!(frozen.offset, (0, 0));
+
+        state.toggle_freeze();
+        assert!(state.frozen.is_none());
+        // 解冻后再冻结，偏移重新从 0 开始
+        state.toggle_freeze();
+        state.nudge(1, 1);
+        state.toggle_freeze();
+        state.toggle_freeze();
+        assert_eq!(offset(&state), (0, 0));
+    }
+
+    #[test]
+    fn freeze_needs_a_square_screen() {
+        let mut state = screen_state(GridBounds::full(5, 5));
+        state.screen_colors.pop();
+        state.toggle_freeze();
+        assert!(state.frozen.is_none());
+
+        state.screen_colors.clear();
+        state.toggle_freeze();
+        assert!(state.frozen.is_none());
+    }
+
+    #[test]
+    fn nudge_stops_at_the_screen_edges() {
+        let mut state = screen_state(GridBounds::full(5, 5));
+        // 没有冻结时不动
+        state.nudge(1, 0);
+        assert!(state.frozen.is_none());
+
+        state.toggle_freeze();
+        state.nudge(1, -1);
+        assert_eq!(offset(&state), (1, -1));
+        state.nudge(10, -10);
+        assert_eq!(offset(&state), (2, -2));
+        state.nudge(-100, 100);
+        assert_eq!(offset(&state), (-2, 2));
+    }
+
+    // 画面左边两列和上边一行在显示器外
+    #[test]
+    fn nudge_stays_on_the_monitor() {
+        let mut state = screen_state(GridBounds {
+            min: (2, 1),
+            max: (5, 5),
+        });
+        state.toggle_freeze();
+        state.nudge(-10, -10);
+        assert_eq!(offset(&state), (0, -1));
+        state.nudge(10, 10);
+        assert_eq!(offset(&state), (2, 2));
+    }
+
+    // 比采样范围还窄或者为空的 bounds 不会 panic
+    #[test]
+    fn nudge_with_narrow_or_empty_bounds() {
+        let mut state = screen_state(GridBounds {
+            min: (4, 0),
+            max: (5, 5),
+        });
+        state.screen_sample_size = 5;
+        state.toggle_freeze();
+        state.nudge(-3, 0);
+        assert_eq!(offset(&state), (2, 0));
+
+        for bounds in [
+            GridBounds::default(),
+            GridBounds {
+                min: (3, 3),
+                max: (2, 2),
+            },
+            GridBounds {
+                min: (7, 0),
+                max: (9, 5),
+            },
+        ] {
+            let mut state = screen_state(bounds);
+            state.toggle_freeze();
+            state.nudge(1, -1);
+            assert_eq!(offset(&state), (0, 0), "{:?}", bounds);
+            state.refresh_frozen();
+        }
+    }
+
+    #[test]
+    fn refresh_while_frozen() {
+        let mut state = screen_state(GridBounds::full(5, 5));
+        // 没有冻结时不改变颜色
+        state.refresh_frozen();
+        assert_eq!(state.color, Color::new(22, 0, 0));
+
+        state.toggle_freeze();
+        state.nudge(1, -2);
+        state.refresh_frozen();
+        assert_eq!(state.position, Position { x: 101, y: 198 });
+        assert_eq!(state.color, Color::new(30, 0, 0));
+
+        // 改变采样设置后按冻结的画面重新计算，3×3 的平均
+        state.screen_sample_size = 3;
+        state.sample_aggregator = SampleAggregator::Mean;
+        state.nudge(-1, 2);
+        state.refresh_frozen();
+        assert_eq!(state.position, Position { x: 100, y: 200 });
+        assert_eq!(state.color, Color::new(22, 0, 0));
+        assert_eq!(state.sample_stats.min, Color::new(11, 0, 0));
+        assert_eq!(state.sample_stats.max, Color::new(33, 0, 0));
+
+        // 屏幕上新的画面不影响冻结的颜色
+        state.screen_colors = vec![Color::new(255, 255, 255); 25];
+        state.refresh_frozen();
+        assert_eq!(state.color, Color::new(22, 0, 0));
+    }
 }
@@ -0,0 +1,955 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    io::Write,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    app::app_state::AppState,
+    model::{
+        color::{Color, parse::ParseColorError},
+        position::Position,
+        readout::Readout,
+        sample_aggregator::SampleAggregator,
+        sample_shape::SampleShape,
+        sample_space::SampleSpace,
+        template::{Template, TemplateError},
+        wheel_mode::WheelMode,
+    },
+    service::{
+        cursor_color::{GetCursorColorError, SampleSettings, get_screen_data, sample_cursor},
+        screen_source::{ScreenSource, default_screen_source},
+    },
+};
+
+pub const USAGE: &str = "\
+usage:
+  colorose [--sample N] [--screen N] [--wheel hsv|hsl] [sample options]
+  colorose pick [--to FORMAT] [sample options]
+  colorose at <x> <y> [--to FORMAT] [sample options]
+  colorose convert <color> [--to FORMAT]
+  colorose stream [--interval 50ms] [sample options]
+
+without a command colorose opens the window, the options preset its buttons
+
+sample options:
+  --sample N        odd sample size, default 1
+  --shape NAME      square, circle or gaussian
+  --aggregate NAME  mean, median, vector-median, mode or trimmed-mean
+  --space NAME      srgb, linear or oklab
+
+stream writes one JSON object per line whenever the position or color changes,
+hsv and hsl use 0-100 for saturation, value and lightness like the readouts
+
+FORMAT is one of rgb hex hsv hsl xyz lab lch lab-d50 lch-d50 oklab oklch xterm ansi,
+or a template such as \"{r},{g},{b}\" (default hex)";
+
+// 命令行参数，没有子命令时打开窗口
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Gui(GuiOptions),
+    Pick {
+        sample: SampleOptions,
+        output: Output,
+    },
+    At {
+        position: Position, // 物理像素坐标
+        sample: SampleOptions,
+        output: Output,
+    },
+    Convert {
+        color: String,
+        output: Output,
+    },
+    Stream {
+        interval: Duration,
+        sample: SampleOptions,
+    },
+    Help,
+}
+
+// 采样设置，没有给出的使用界面的默认值
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SampleOptions {
+    pub size: Option<usize>,
+    pub shape: Option<SampleShape>,
+    pub aggregator: Option<SampleAggregator>,
+    pub space: Option<SampleSpace>,
+}
+
+// 打开窗口时预设的状态
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GuiOptions {
+    pub sample: SampleOptions,
+    pub screen_size: Option<usize>,
+    pub wheel_mode: Option<WheelMode>,
+}
+
+// 输出格式，读数行的名字或模板
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Output {
+    Readout(Readout),
+    Template(Template),
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    UnknownCommand(String),
+    UnknownOption(String),
+    UnexpectedArgument(String),
+    MissingArgument(&'static str),
+    MissingValue(String),
+    InvalidValue { option: String, value: String },
+    Template(TemplateError),
+    Color(ParseColorError),
+    Screen(GetCursorColorError),
+    Io(std::io::Error),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::UnknownCommand(command) => write!(f, "unknown command: {}", command),
+            CliError::UnknownOption(option) => write!(f, "unknown option: {}", option),
+            CliError::UnexpectedArgument(arg) => write!(f, "unexpected argument: {}", arg),
+            CliError::MissingArgument(name) => write!(f, "missing argument: {}", name),
+            CliError::MissingValue(option) => write!(f, "{} needs a value", option),
+            CliError::InvalidValue { option, value } => {
+                write!(f, "invalid value for {}: {}", option, value)
+            }
+            CliError::Template(e) => write!(f, "invalid template: {}", e),
+            CliError::Color(e) => write!(f, "invalid color: {}", e),
+            CliError::Screen(e) => write!(f, "{}", e),
+            CliError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for CliError {}
+
+impl Default for Output {
+    fn default() -> Self {
+        Output::Readout(Readout::Hex)
+    }
+}
+
+impl Output {
+    // 先按读数行的名字查找，含有 { 时当作模板
+    pub fn parse(value: &str) -> Result<Self, CliError> {
+        if let Some(readout) = Readout::from_key(value) {
+            return Ok(Output::Readout(readout));
+        }
+        if value.contains('{') {
+            return Template::parse(value)
+                .map(Output::Template)
+                .map_err(CliError::Template);
+        }
+        Err(CliError::InvalidValue {
+            option: "--to".to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    pub fn render(&self, color: &Color) -> String {
+        match self {
+            Output::Readout(readout) => readout.format(color),
+            Output::Template(template) => template.render(color),
+        }
+    }
+}
+
+impl SampleOptions {
+    pub const MAX_SIZE: usize = 25;
+
+    pub fn is_single_pixel(&self) -> bool {
+        self.size.unwrap_or(1) == 1
+    }
+
+    // 不需要放大镜画面，画面只取采样区域大小
+    pub fn settings(&self) -> SampleSettings {
+        let size = self.size.unwrap_or(1);
+        SampleSettings {
+            tex_size: size,
+            sample_size: size,
+            shape: self.shape.unwrap_or_default(),
+            aggregator: self.aggregator.unwrap_or_default(),
+            space: self.space.unwrap_or_default(),
+        }
+    }
+
+    // 不打开窗口时在 position 处取一次色
+    pub fn sample(
+        &self,
+        source: &(impl ScreenSource + ?Sized),
+        position: Position,
+    ) -> Result<Color, GetCursorColorError> {
+        let data = get_screen_data(source, position, &self.settings())?;
+        Ok(data.cursor_pixel_color)
+    }
+}
+
+impl GuiOptions {
+    pub fn apply(&self, state: &mut AppState) {
+        if let Some(size) = self.screen_size {
+            state.screen_tex_size = size;
+        }
+        if let Some(size) = self.sample.size {
+            state.screen_sample_size = size.min(state.screen_tex_size);
+        }
+        if let Some(shape) = self.sample.shape {
+            state.sample_shape = shape;
+        }
+        if let Some(aggregator) = self.sample.aggregator {
+            state.sample_aggregator = aggregator;
+        }
+        if let Some(space) = self.sample.space {
+            state.sample_space = space;
+        }
+        if let Some(mode) = self.wheel_mode {
+            state.wheel_mode = mode;
+        }
+    }
+}
+
+impl Command {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut args = args.into_iter().peekable();
+        let command = match args.peek().map(String::as_str) {
+            Some("pick" | "at" | "convert" | "stream" | "help") => args.next(),
+            Some(arg) if !arg.starts_with('-') => {
+                return Err(CliError::UnknownCommand(arg.to_string()));
+            }
+            _ => None,
+        };
+
+        let mut sample = SampleOptions::default();
+        let mut gui = GuiOptions::default();
+        let mut output = Output::default();
+        let mut interval = Duration::from_millis(50);
+        let mut positional = vec![];
+        while let Some(arg) = args.next() {
+            if matches!(arg.as_str(), "-h" | "--help") {
+                return Ok(Command::Help);
+            }
+            // 负坐标如 -10 不是选项
+            if !arg.starts_with("--") {
+                positional.push(arg);
+                continue;
+            }
+            // --name value 或 --name=value
+            let (option, inline) = match arg.split_once('=') {
+                Some((option, value)) => (option.to_string(), Some(value.to_string())),
+                None => (arg.clone(), None),
+            };
+            let value = inline
+                .or_else(|| args.next())
+                .ok_or_else(|| CliError::MissingValue(option.clone()))?;
+            let invalid = || CliError::InvalidValue {
+                option: option.clone(),
+                value: value.clone(),
+            };
+            match option.as_str() {
+                "--to" | "--format" if command.as_deref() != Some("stream") => {
+                    output = Output::parse(&value)?
+                }
+                "--interval" if command.as_deref() == Some("stream") => {
+                    interval = parse_interval(&value).ok_or_else(invalid)?
+                }
+                "--sample" => sample.size = Some(parse_size(&value).ok_or_else(invalid)?),
+                "--shape" => {
+                    sample.shape =
+                        Some(choice(&value, &SampleShape::ALL, |s| s.name()).ok_or_else(invalid)?)
+                }
+                "--aggregate" => {
+                    sample.aggregator = Some(
+                        choice(&value, &SampleAggregator::ALL, |a| a.name()).ok_or_else(invalid)?,
+                    )
+                }
+                "--space" => {
+                    sample.space =
+                        Some(choice(&value, &SampleSpace::ALL, |s| s.name()).ok_or_else(invalid)?)
+                }
+                "--screen" if command.is_none() => {
+                    gui.screen_size = Some(parse_size(&value).ok_or_else(invalid)?)
+                }
+                "--wheel" if command.is_none() => {
+                    gui.wheel_mode =
+                        Some(choice(&value, &WheelMode::ALL, |m| m.name()).ok_or_else(invalid)?)
+                }
+                _ => return Err(CliError::UnknownOption(option)),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let command = match command.as_deref() {
+            None => {
+                gui.sample = sample;
+                Command::Gui(gui)
+            }
+            Some("help") => Command::Help,
+            Some("pick") => Command::Pick { sample, output },
+            Some("stream") => Command::Stream { interval, sample },
+            Some("at") => {
+                let mut coordinate = |name| {
+                    let value = positional.next().ok_or(CliError::MissingArgument(name))?;
+                    value.parse::<i32>().map_err(|_| CliError::InvalidValue {
+                        option: name.to_string(),
+                        value,
+                    })
+                };
+                let position = Position {
+                    x: coordinate("x")?,
+                    y: coordinate("y")?,
+                };
+                Command::At {
+                    position,
+                    sample,
+                    output,
+                }
+            }
+            Some(_) => {
+                let color = positional
+                    .next()
+                    .ok_or(CliError::MissingArgument("color"))?;
+                if sample != SampleOptions::default() {
+                    return Err(CliError::UnexpectedArgument("sample options".to_string()));
+                }
+                Command::Convert { color, output }
+            }
+        };
+        match positional.next() {
+            Some(arg) => Err(CliError::UnexpectedArgument(arg)),
+            None => Ok(command),
+        }
+    }
+
+    // 执行不需要窗口的命令，结果写到 out
+    pub fn run(&self, out: &mut impl Write) -> Result<(), CliError> {
+        let line = match self {
+            Command::Stream { interval, sample } => return stream(*interval, sample, out),
+            Command::Gui(_) | Command::Help => USAGE.to_string(),
+            Command::Pick { sample, output } => {
+                let source = default_screen_source();
+                // Wayland 下需要用户在门户中点击取色，单个像素直接用门户给出的颜色，
+                // 否则在截图中定位所选的颜色后按采样设置重新采样
+                if source.needs_explicit_pick() {
+                    if sample.is_single_pixel() {
+                        let color = source.pick_color().map_err(CliError::Screen)?;
+                        return writeln!(out, "{}", output.render(&color)).map_err(CliError::Io);
+                    }
+                    source.pick().map_err(CliError::Screen)?;
+                }
+                let position = source.cursor_position().map_err(CliError::Screen)?;
+                output.render(
+                    &sample
+                        .sample(&*source, position)
+                        .map_err(CliError::Screen)?,
+                )
+            }
+            Command::At {
+                position,
+                sample,
+                output,
+            } => {
+                let source = default_screen_source();
+                output.render(
+                    &sample
+                        .sample(&*source, *position)
+                        .map_err(CliError::Screen)?,
+                )
+            }
+            Command::Convert { color, output } => {
+                output.render(&parse_color(color).map_err(CliError::Color)?)
+            }
+        };
+        writeln!(out, "{}", line).map_err(CliError::Io)
+    }
+}
+
+fn stream(
+    interval: Duration,
+    sample: &SampleOptions,
+    out: &mut impl Write,
+) -> Result<(), CliError> {
+    let source = default_screen_source();
+    // Wayland 下不能持续读取鼠标位置
+    if source.needs_explicit_pick() {
+        return Err(CliError::Screen(
+            GetCursorColorError::UnableGetMousePosition,
+        ));
+    }
+    stream_from(&*source, interval, &sample.settings(), out)
+}
+
+// 和窗口的后台线程一样循环采样，位置或颜色变化时写出一行 JSON，
+// 输出被关闭时正常结束
+fn stream_from(
+    source: &(impl ScreenSource + ?Sized),
+    interval: Duration,
+    settings: &SampleSettings,
+    out: &mut impl Write,
+) -> Result<(), CliError> {
+    let mut last: Option<(Position, Color)> = None;
+    loop {
+        match sample_cursor(source, settings) {
+            Ok((position, data)) => {
+                let color = data.cursor_pixel_color;
+                if last != Some((position, color)) {
+                    last = Some((position, color));
+                    let line = stream_line(position, &color, settings);
+                    match writeln!(out, "{}", line).and_then(|_| out.flush()) {
+                        Ok(()) => {}
+                        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => return Ok(()),
+                        Err(e) => return Err(CliError::Io(e)),
+                    }
+                }
+            }
+            // 第一次就失败时报错，之后的失败当作暂时的，跳过这一次
+            Err(e) if last.is_none() => return Err(CliError::Screen(e)),
+            Err(_) => {}
+        }
+        thread::sleep(interval);
+    }
+}
+
+// 饱和度、明度和亮度与界面的读数一样为 0~100
+fn stream_line(position: Position, color: &Color, settings: &SampleSettings) -> String {
+    let round = |value: f32| (value as f64 * 1000.0).round() / 1000.0;
+    let hsv = color.to_hsv();
+    let hsl = color.to_hsl();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    serde_json::json!({
+        "timestamp": timestamp,
+        "position": { "x": position.x, "y": position.y },
+        "rgb": { "r": color.r, "g": color.g, "b": color.b },
+        "hex": color.to_hex(),
+        "hsv": { "h": round(hsv.h), "s": round(hsv.s * 100.0), "v": round(hsv.v * 100.0) },
+        "hsl": { "h": round(hsl.h), "s": round(hsl.s * 100.0), "l": round(hsl.l * 100.0) },
+        "sample": {
+            "size": settings.sample_size,
+            "shape": option_value(settings.shape.name()),
+            "aggregate": option_value(settings.aggregator.name()),
+            "space": option_value(settings.space.name()),
+        },
+    })
+    .to_string()
+}
+
+// 命令行里 # 容易被当成注释，不是颜色名时也接受不带 # 的十六进制
+fn parse_color(text: &str) -> Result<Color, ParseColorError> {
+    match text.parse::<Color>() {
+        Err(e @ ParseColorError::UnknownName(_)) => Color::from_hex(text).or(Err(e)),
+        result => result,
+    }
+}
+
+// 显示的名字转成命令行参数的写法，如 vector-median
+fn option_value(name: &str) -> String {
+    name.to_lowercase().replace(' ', "-")
+}
+
+// 如 50ms、1s、0.5s，不带单位时为毫秒
+fn parse_interval(value: &str) -> Option<Duration> {
+    let (number, scale) = if let Some(ms) = value.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(s) = value.strip_suffix('s') {
+        (s, 1.0)
+    } else {
+        (value, 0.001)
+    };
+    let seconds = number.parse::<f64>().ok()? * scale;
+    (seconds.is_finite() && seconds > 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+// 1~MAX_SIZE 的奇数
+fn parse_size(value: &str) -> Option<usize> {
+    let size = value.parse::<usize>().ok()?;
+    (size % 2 == 1 && size <= SampleOptions::MAX_SIZE).then_some(size)
+}
+
+// 按显示的名字匹配，不区分大小写，- 和 _ 当作空格，也可以只写第一个词
+fn choice<T: Copy>(value: &str, all: &[T], name: impl Fn(&T) -> &'static str) -> Option<T> {
+    let value = value.to_lowercase().replace(['-', '_'], " ");
+    all.iter().copied().find(|item| {
+        let name = name(item).to_lowercase();
+        name == value || name.split(' ').next() == Some(value.as_str())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+        io::{self, Write},
+        rc::Rc,
+        time::Duration,
+    };
+
+    use image::{Rgba, RgbaImage};
+
+    use crate::{
+        app::cli::{
+            CliError, Command, GuiOptions, Output, SampleOptions, choice, parse_color,
+            parse_interval, parse_size, stream_from, stream_line,
+        },
+        model::{
+            color::{Color, parse::ParseColorError},
+            position::Position,
+            readout::Readout,
+            sample_aggregator::SampleAggregator,
+            sample_shape::SampleShape,
+            sample_space::SampleSpace,
+            template::{Template, TemplateError},
+            wheel_mode::WheelMode,
+        },
+        service::{
+            cursor_color::GetCursorColorError,
+            screen_source::{Monitor, ScreenSource, mock::MockScreenSource},
+        },
+    };
+
+    fn parse(args: &[&str]) -> Result<Command, CliError> {
+        Command::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    // 按顺序给出鼠标位置，None 表示这一次读取失败；
+    // 用完后移到 (9, 9) 并标记 done
+    struct Scripted {
+        screen: MockScreenSource,
+        cursors: RefCell<VecDeque<Option<(i32, i32)>>>,
+        done: Rc<Cell<bool>>,
+    }
+
+    impl ScreenSource for Scripted {
+        fn monitors(&self) -> Result<Vec<Monitor>, GetCursorColorError> {
+            self.screen.monitors()
+        }
+
+        fn cursor_position(&self) -> Result<Position, GetCursorColorError> {
+            let Some(cursor) = self.cursors.borrow_mut().pop_front() else {
+                self.done.set(true);
+                return Ok(Position { x: 9, y: 9 });
+            };
+            let (x, y) = cursor.ok_or(GetCursorColorError::UnableGetMousePosition)?;
+            Ok(Position { x, y })
+        }
+
+        fn capture_area(
+            &self,
+            monitor: &Monitor,
+            x: i32,
+            y: i32,
+            width: u32,
+            height: u32,
+        ) -> Result<RgbaImage, GetCursorColorError> {
+            self.screen.capture_area(monitor, x, y, width, height)
+        }
+    }
+
+    // done 之后写入返回 error
+    struct Pipe {
+        text: Vec<u8>,
+        done: Rc<Cell<bool>>,
+        error: io::ErrorKind,
+    }
+
+    impl Write for Pipe {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.done.get() {
+                return Err(self.error.into());
+            }
+            self.text.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // 左半红右半蓝
+    fn run_stream(
+        cursors: &[Option<(i32, i32)>],
+        error: io::ErrorKind,
+    ) -> (Result<(), CliError>, Vec<serde_json::Value>) {
+        let image = RgbaImage::from_fn(10, 10, |x, _| {
+            if x < 5 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 255])
+            }
+        });
+        let done = Rc::new(Cell::new(false));
+        let source = Scripted {
+            screen: MockScreenSource::new(image),
+            cursors: RefCell::new(cursors.iter().copied().collect()),
+            done: done.clone(),
+        };
+        let mut out = Pipe {
+            text: vec![],
+            done,
+            error,
+        };
+        let result = stream_from(
+            &source,
+            Duration::ZERO,
+            &SampleOptions::default().settings(),
+            &mut out,
+        );
+        let lines = String::from_utf8(out.text)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        (result, lines)
+    }
+
+    #[test]
+    fn convert_accepts_bare_hex() {
+        assert_eq!(parse_color("ff0000"), Ok(Color::new(255, 0, 0)));
+        assert_eq!(parse_color("cafe"), Ok(Color::new_rgba(204, 170, 255, 238)));
+        // 颜色名优先
+        assert_eq!(parse_color("red"), Ok(Color::new(255, 0, 0)));
+        assert_eq!(
+            parse_color("nothex"),
+            Err(ParseColorError::UnknownName("nothex".to_string()))
+        );
+        assert_eq!(parse_color("rgb(1 2)"), "rgb(1 2)".parse::<Color>());
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(parse(&[]).unwrap(), Command::Gui(GuiOptions::default()));
+        assert_eq!(
+            parse(&[
+                "--sample",
+                "5",
+                "--screen=21",
+                "--wheel",
+                "hsl",
+                "--shape",
+                "circle"
+            ])
+            .unwrap(),
+            Command::Gui(GuiOptions {
+                sample: SampleOptions {
+                    size: Some(5),
+                    shape: Some(SampleShape::Circle),
+                    ..Default::default()
+                },
+                screen_size: Some(21),
+                wheel_mode: Some(WheelMode::HSL),
+            })
+        );
+        assert_eq!(
+            parse(&["pick", "--to", "oklch", "--aggregate=vector-median"]).unwrap(),
+            Command::Pick {
+                sample: SampleOptions {
+                    aggregator: Some(SampleAggregator::VectorMedian),
+                    ..Default::default()
+                },
+                output: Output::Readout(Readout::OKLCh),
+            }
+        );
+        // 负坐标不是选项
+        assert_eq!(
+            parse(&["at", "-10", "20", "--space", "oklab", "--format={hex}"]).unwrap(),
+            Command::At {
+                position: Position { x: -10, y: 20 },
+                sample: SampleOptions {
+                    space: Some(SampleSpace::OKLab),
+                    ..Default::default()
+                },
+                output: Output::Template(Template::parse("{hex}").unwrap()),
+            }
+        );
+        assert_eq!(
+            parse(&["convert", "#336699"]).unwrap(),
+            Command::Convert {
+                color: "#336699".to_string(),
+                output: Output::default(),
+            }
+        );
+        assert_eq!(
+            parse(&["stream", "--interval", "1s", "--sample", "3"]).unwrap(),
+            Command::Stream {
+                interval: Duration::from_secs(1),
+                sample: SampleOptions {
+                    size: Some(3),
+                    ..Default::default()
+                },
+            }
+        );
+        assert_eq!(
+            parse(&["stream"]).unwrap(),
+            Command::Stream {
+                interval: Duration::from_millis(50),
+                sample: SampleOptions::default(),
+            }
+        );
+        for args in [
+            &["help"][..],
+            &["-h"],
+            &["--help"],
+            &["pick", "--sample", "3", "-h"],
+        ] {
+            assert_eq!(parse(args).unwrap(), Command::Help);
+        }
+    }
+
+    #[test]
+    fn parse_rejects_bad_arguments() {
+        assert!(matches!(parse(&["picker"]), Err(CliError::UnknownCommand(c)) if c == "picker"));
+        // 选项只在对应的命令中可用
+        assert!(matches!(
+            parse(&["--interval", "1s"]),
+            Err(CliError::UnknownOption(o)) if o == "--interval"
+        ));
+        assert!(matches!(
+            parse(&["pick", "--screen", "9"]),
+            Err(CliError::UnknownOption(o)) if o == "--screen"
+        ));
+        assert!(matches!(
+            parse(&["pick", "--wheel=hsv"]),
+            Err(CliError::UnknownOption(o)) if o == "--wheel"
+        ));
+        assert!(matches!(
+            parse(&["stream", "--to", "hex"]),
+            Err(CliError::UnknownOption(o)) if o == "--to"
+        ));
+        assert!(matches!(
+            parse(&["pick", "--sample"]),
+            Err(CliError::MissingValue(o)) if o == "--sample"
+        ));
+        assert!(matches!(
+            parse(&["--sample", "4"]),
+            Err(CliError::InvalidValue { option, value }) if option == "--sample" && value == "4"
+        ));
+        assert!(matches!(
+            parse(&["--shape=star"]),
+            Err(CliError::InvalidValue { option, value }) if option == "--shape" && value == "star"
+        ));
+        assert!(matches!(
+            parse(&["stream", "--interval", "0"]),
+            Err(CliError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            parse(&["at", "1.5", "2"]),
+            Err(CliError::InvalidValue { option, value }) if option == "x" && value == "1.5"
+        ));
+        assert!(matches!(
+            parse(&["at", "1"]),
+            Err(CliError::MissingArgument("y"))
+        ));
+        assert!(matches!(
+            parse(&["convert"]),
+            Err(CliError::MissingArgument("color"))
+        ));
+        assert!(matches!(
+            parse(&["at", "1", "2", "3"]),
+            Err(CliError::UnexpectedArgument(a)) if a == "3"
+        ));
+        assert!(matches!(
+            parse(&["pick", "extra"]),
+            Err(CliError::UnexpectedArgument(a)) if a == "extra"
+        ));
+        // convert 不采样
+        assert!(matches!(
+            parse(&["convert", "red", "--sample", "3"]),
+            Err(CliError::UnexpectedArgument(a)) if a == "sample options"
+        ));
+        assert!(matches!(
+            parse(&["convert", "red", "--to", "{r"]),
+            Err(CliError::Template(TemplateError::UnclosedBrace(0)))
+        ));
+    }
+
+    #[test]
+    fn output_is_a_readout_key_or_a_template() {
+        assert_eq!(Output::parse("hex").unwrap(), Output::Readout(Readout::Hex));
+        assert_eq!(
+            Output::parse("LAB-D50").unwrap(),
+            Output::Readout(Readout::LabD50)
+        );
+        assert_eq!(
+            Output::parse("{r},{g},{b}")
+                .unwrap()
+                .render(&Color::new(1, 2, 3)),
+            "1,2,3"
+        );
+        assert!(matches!(
+            Output::parse("{nope}"),
+            Err(CliError::Template(TemplateError::UnknownVariable(v))) if v == "nope"
+        ));
+        // 不含 { 的未知名字不当作模板
+        assert!(matches!(
+            Output::parse("cmyk"),
+            Err(CliError::InvalidValue { option, value }) if option == "--to" && value == "cmyk"
+        ));
+    }
+
+    #[test]
+    fn intervals() {
+        assert_eq!(parse_interval("50ms"), Some(Duration::from_millis(50)));
+        assert_eq!(parse_interval("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_interval("0.5s"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_interval("250"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_interval("1e3"), Some(Duration::from_secs(1)));
+        for value in ["0", "0s", "-5ms", "", "ms", "fast", "inf", "NaN"] {
+            assert_eq!(parse_interval(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn sizes_are_odd_and_at_most_the_maximum() {
+        for size in (1..=SampleOptions::MAX_SIZE).step_by(2) {
+            assert_eq!(parse_size(&size.to_string()), Some(size));
+        }
+        for value in ["0", "2", "24", "27", "-1", "3.0", "", "five"] {
+            assert_eq!(parse_size(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn choices_match_display_names() {
+        let aggregator = |value| choice(value, &SampleAggregator::ALL, |a| a.name());
+        assert_eq!(aggregator("mean"), Some(SampleAggregator::Mean));
+        assert_eq!(aggregator("MEDIAN"), Some(SampleAggregator::Median));
+        assert_eq!(
+            aggregator("vector-median"),
+            Some(SampleAggregator::VectorMedian)
+        );
+        assert_eq!(
+            aggregator("trimmed_mean"),
+            Some(SampleAggregator::TrimmedMean)
+        );
+        // 只写第一个词
+        assert_eq!(aggregator("vector"), Some(SampleAggregator::VectorMedian));
+        assert_eq!(aggregator("trimmed"), Some(SampleAggregator::TrimmedMean));
+        assert_eq!(aggregator("median vector"), None);
+        assert_eq!(aggregator("med"), None);
+
+        let space = |value| choice(value, &SampleSpace::ALL, |s| s.name());
+        assert_eq!(space("srgb"), Some(SampleSpace::SRGB));
+        assert_eq!(space("linear"), Some(SampleSpace::Linear));
+        assert_eq!(space("Linear-Light"), Some(SampleSpace::Linear));
+        assert_eq!(space("light"), None);
+    }
+
+    #[test]
+    fn stream_writes_a_json_line_per_change() {
+        let cursors = [
+            (2, 2),
+            (2, 2),
+            (3, 2),
+            (7, 2),
+            (7, 2),
+            (8, 2),
+            (2, 2),
+            (2, 2),
+        ];
+        let (result, lines) = run_stream(&cursors.map(Some), io::ErrorKind::BrokenPipe);
+        // 输出被关闭时正常结束
+        assert!(result.is_ok());
+        let samples: Vec<(i64, &str)> = lines
+            .iter()
+            .map(|line| {
+                assert!(line.is_object());
+                assert!(line["timestamp"].is_u64());
+                (
+                    line["position"]["x"].as_i64().unwrap(),
+                    line["hex"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            samples,
+            [
+                (2, "#FF0000"),
+                (3, "#FF0000"),
+                (7, "#0000FF"),
+                (8, "#0000FF"),
+                (2, "#FF0000")
+            ]
+        );
+        assert_eq!(
+            lines[0]["rgb"],
+            serde_json::json!({ "r": 255, "g": 0, "b": 0 })
+        );
+        assert_eq!(
+            lines[0]["sample"],
+            serde_json::json!({ "size": 1, "shape": "square", "aggregate": "mean", "space": "srgb" })
+        );
+    }
+
+    #[test]
+    fn stream_skips_failures_after_the_first_sample() {
+        let (result, lines) = run_stream(
+            &[Some((2, 2)), None, Some((2, 2)), None, Some((7, 2))],
+            io::ErrorKind::BrokenPipe,
+        );
+        assert!(result.is_ok());
+        assert_eq!(lines.len(), 2);
+
+        let (result, lines) = run_stream(&[None, Some((2, 2))], io::ErrorKind::BrokenPipe);
+        assert!(matches!(
+            result,
+            Err(CliError::Screen(
+                GetCursorColorError::UnableGetMousePosition
+            ))
+        ));
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn stream_reports_other_write_errors() {
+        let (result, lines) = run_stream(&[Some((2, 2))], io::ErrorKind::PermissionDenied);
+        assert!(
+            matches!(result, Err(CliError::Io(e)) if e.kind() == io::ErrorKind::PermissionDenied)
+        );
+        assert_eq!(lines.len(), 1);
+    }
+
+    #[test]
+    fn stream_line_uses_readout_units() {
+        let settings = SampleOptions {
+            size: Some(5),
+            aggregator: Some(SampleAggregator::VectorMedian),
+            space: Some(SampleSpace::Linear),
+            ..Default::default()
+        }
+        .settings();
+        let line: serde_json::Value = serde_json::from_str(&stream_line(
+            Position { x: -3, y: 4 },
+            &Color::new(51, 102, 153),
+            &settings,
+        ))
+        .unwrap();
+        assert_eq!(line["position"], serde_json::json!({ "x": -3, "y": 4 }));
+        assert_eq!(line["hex"], "#336699");
+        // 与界面的读数一样为 0~100
+        assert_eq!(
+            line["hsv"],
+            serde_json::json!({ "h": 210.0, "s": 66.667, "v": 60.0 })
+        );
+        assert_eq!(
+            line["hsl"],
+            serde_json::json!({ "h": 210.0, "s": 50.0, "l": 40.0 })
+        );
+        assert_eq!(
+            line["sample"],
+            serde_json::json!({
+                "size": 5,
+                "shape": "square",
+                "aggregate": "vector-median",
+                "space": "linear-light"
+            })
+        );
+    }
+}
@@ -1,4 +1,6 @@
 pub mod app_state;
+pub mod cli;
+#[allow(clippy::module_inception)]
 pub mod app;
 
 pub use app::App as App;
\ No newline at end of file
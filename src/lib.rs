@@ -0,0 +1,4 @@
+pub mod app;
+pub mod model;
+pub mod service;
+pub mod ui;
@@ -1,14 +1,28 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-mod app;
-mod model;
-mod service;
-mod ui;
-
-use crate::app::App;
+use colorose::app::{
+    App,
+    cli::{Command, USAGE},
+};
+use colorose::service::platform::{Platform, current_platform};
 
 use std::error::Error;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    App::run()?;
+    // 有子命令时不打开窗口
+    match Command::parse(std::env::args().skip(1)) {
+        Ok(Command::Gui(options)) => App::run(options)?,
+        Ok(command) => {
+            current_platform().attach_console();
+            if let Err(e) = command.run(&mut std::io::stdout()) {
+                eprintln!("colorose: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            current_platform().attach_console();
+            eprintln!("colorose: {}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    }
     Ok(())
 }
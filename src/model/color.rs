@@ -1,11 +1,20 @@
 use egui::Color32;
-use std::fmt::Display;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{error::Error, fmt::Display};
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub mod ansi;
+pub mod cie;
+pub mod contrast;
+pub mod difference;
+pub mod oklab;
+pub mod parse;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
     pub b: u8,
+    pub a: u8, // 255 为不透明
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
@@ -13,33 +22,72 @@ pub struct HSL {
     pub h: f32, // 0..=360
     pub s: f32, // 0..=1
     pub l: f32, // 0..=1
+    pub a: f32, // 0..=1
 }
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct HSV {
     pub h: f32, // 色相 (0°~360°)
     pub s: f32, // 饱和度 (0.0~1.0)
     pub v: f32, // 明度 (0.0~1.0)
+    pub a: f32, // 不透明度 (0.0~1.0)
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Color::new(0, 0, 0)
+    }
 }
 
 impl Display for Color {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "r:{:} g:{:} b:{:}", self.r, self.g, self.b)
+        write!(f, "r:{:} g:{:} b:{:}", self.r, self.g, self.b)?;
+        if !self.is_opaque() {
+            write!(f, " a:{:}", self.a)?;
+        }
+        Ok(())
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<String> for Color {
     fn into(self) -> String {
-        format!("r:{:} g:{:} b:{:}", self.r, self.g, self.b)
+        self.to_string()
+    }
+}
+
+// 保存为 hex 字符串，不透明时省略 alpha
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex_auto())
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        Color::from_hex(&hex).map_err(serde::de::Error::custom)
     }
 }
 
 impl Color {
     pub fn new(r: u8, g: u8, b: u8) -> Self {
-        Self { r, g, b }
+        Self { r, g, b, a: 255 }
+    }
+
+    pub fn new_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.a == 255
+    }
+
+    pub fn alpha(&self) -> f32 {
+        self.a as f32 / 255.0
     }
 
     pub fn to_color32(&self) -> Color32 {
-        Color32::from_rgb(self.r, self.g, self.b)
+        Color32::from_rgba_unmultiplied(self.r, self.g, self.b, self.a)
     }
 
     pub fn revert(&self) -> Self {
@@ -47,9 +95,11 @@ impl Color {
             r: 255 - self.r,
             g: 255 - self.g,
             b: 255 - self.b,
+            a: self.a,
         }
     }
 
+    #[allow(clippy::manual_clamp)]
     pub fn to_hsl(&self) -> HSL {
         let r = self.r as f32 / 255.0;
         let g = self.g as f32 / 255.0;
@@ -84,6 +134,7 @@ impl Color {
             h: if h < 0.0 { h + 360.0 } else { h }, // 确保在0-360 范围内
             s: s.max(0.0).min(1.0),                 // 钳制到0-1
             l: l.max(0.0).min(1.0),                 // 钳制到0-1
+            a: self.alpha(),
         }
     }
 
@@ -115,34 +166,39 @@ impl Color {
             h: if h < 0.0 { h + 360.0 } else { h }, // 确保色相在0-360°
             s: s.clamp(0.0, 1.0),                   // 钳制饱和度
             v: max.clamp(0.0, 1.0),                 // 明度直接取最大值
+            a: self.alpha(),
         }
     }
 }
 
 impl From<Color32> for Color {
     fn from(value: Color32) -> Self {
-        Color {
-            r: value.r(),
-            g: value.g(),
-            b: value.b(),
-        }
+        // Color32 是预乘的，先还原
+        let [r, g, b, a] = value.to_srgba_unmultiplied();
+        Color { r, g, b, a }
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<String> for HSL {
     fn into(self) -> String {
-        format!(
+        let hsl = format!(
             "h:{:.1} s:{:.1} l:{:.1}",
             self.h,
             self.s * 100.0,
             self.l * 100.0
-        )
+        );
+        if self.a < 1.0 {
+            format!("{} a:{:.2}", hsl, self.a)
+        } else {
+            hsl
+        }
     }
 }
 
 impl HSL {
     pub fn new(h: f32, s: f32, l: f32) -> Self {
-        Self { h, s, l }
+        Self { h, s, l, a: 1.0 }
     }
 
     pub fn to_rgb(&self) -> Color {
@@ -160,6 +216,7 @@ impl HSL {
                 r: value,
                 g: value,
                 b: value,
+                a: to_u8(self.a),
             };
         }
 
@@ -193,18 +250,88 @@ impl HSL {
             r: to_u8(hue_to_rgb(h + 1.0 / 3.0)),
             g: to_u8(hue_to_rgb(h)),
             b: to_u8(hue_to_rgb(h - 1.0 / 3.0)),
+            a: to_u8(self.a),
+        }
+    }
+
+    pub fn to_hsv(&self) -> HSV {
+        let v = self.l + self.s * self.l.min(1.0 - self.l);
+        let s = if v == 0.0 {
+            0.0
+        } else {
+            2.0 * (1.0 - self.l / v)
+        };
+
+        HSV {
+            h: self.h,
+            s: s.clamp(0.0, 1.0),
+            v: v.clamp(0.0, 1.0),
+            a: self.a,
         }
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<String> for HSV {
     fn into(self) -> String {
-        format!(
+        let hsv = format!(
             "h:{:.1} s:{:.1} v:{:.1}",
             self.h,
             self.s * 100.0,
             self.v * 100.0
-        )
+        );
+        if self.a < 1.0 {
+            format!("{} a:{:.2}", hsv, self.a)
+        } else {
+            hsv
+        }
+    }
+}
+
+impl HSV {
+    pub fn new(h: f32, s: f32, v: f32) -> Self {
+        Self { h, s, v, a: 1.0 }
+    }
+
+    pub fn to_rgb(&self) -> Color {
+        let to_u8 = |x: f32| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        let c = self.v * self.s; // 色度
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let m = self.v - c;
+
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+
+        Color {
+            r: to_u8(r + m),
+            g: to_u8(g + m),
+            b: to_u8(b + m),
+            a: to_u8(self.a),
+        }
+    }
+
+    pub fn to_hsl(&self) -> HSL {
+        let l = self.v * (1.0 - self.s / 2.0);
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            (self.v - l) / l.min(1.0 - l)
+        };
+
+        HSL {
+            h: self.h,
+            s: s.clamp(0.0, 1.0),
+            l: l.clamp(0.0, 1.0),
+            a: self.a,
+        }
     }
 }
 
@@ -212,4 +339,200 @@ impl Color {
     pub fn to_hex(&self) -> String {
         format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b,)
     }
+
+    // #RRGGBBAA
+    pub fn to_hex_alpha(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+    }
+
+    // #AARRGGBB，Android 等使用
+    pub fn to_hex_argb(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}{:02X}", self.a, self.r, self.g, self.b)
+    }
+
+    // 不透明时输出 #RRGGBB，否则输出 #RRGGBBAA
+    pub fn to_hex_auto(&self) -> String {
+        if self.is_opaque() {
+            self.to_hex()
+        } else {
+            self.to_hex_alpha()
+        }
+    }
+
+    // CSS 的 rgba(r, g, b, a)，a 为 0~1
+    pub fn to_rgba_string(&self) -> String {
+        let a = format!("{:.3}", self.alpha());
+        let a = a.trim_end_matches('0').trim_end_matches('.');
+        format!("rgba({}, {}, {}, {})", self.r, self.g, self.b, a)
+    }
+
+    // #RGB、#RGBA、#RRGGBB 或 #RRGGBBAA，# 可省略
+    pub fn from_hex(hex: &str) -> Result<Self, ColorConversionError> {
+        let hex = hex.trim();
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ColorConversionError::InvalidHex(hex.to_string()));
+        }
+        let channel = |i: usize, width: usize| {
+            let value = u8::from_str_radix(&digits[i * width..(i + 1) * width], 16).unwrap_or(0);
+            if width == 1 { value * 17 } else { value }
+        };
+
+        match digits.len() {
+            3 => Ok(Color::new(channel(0, 1), channel(1, 1), channel(2, 1))),
+            4 => Ok(Color::new_rgba(
+                channel(0, 1),
+                channel(1, 1),
+                channel(2, 1),
+                channel(3, 1),
+            )),
+            6 => Ok(Color::new(channel(0, 2), channel(1, 2), channel(2, 2))),
+            8 => Ok(Color::new_rgba(
+                channel(0, 2),
+                channel(1, 2),
+                channel(2, 2),
+                channel(3, 2),
+            )),
+            _ => Err(ColorConversionError::InvalidHex(hex.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorConversionError {
+    InvalidLength(usize),
+    OutOfRange,
+    InvalidHex(String),
+}
+
+impl Display for ColorConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorConversionError::InvalidLength(len) => {
+                write!(f, "expected 3 or 4 components, got {}", len)
+            }
+            ColorConversionError::OutOfRange => write!(f, "component out of range"),
+            ColorConversionError::InvalidHex(hex) => write!(f, "invalid hex color: {}", hex),
+        }
+    }
+}
+
+impl Error for ColorConversionError {}
+
+impl From<HSL> for Color {
+    fn from(value: HSL) -> Self {
+        value.to_rgb()
+    }
+}
+
+impl From<HSV> for Color {
+    fn from(value: HSV) -> Self {
+        value.to_rgb()
+    }
+}
+
+impl From<Color> for HSL {
+    fn from(value: Color) -> Self {
+        value.to_hsl()
+    }
+}
+
+impl From<HSV> for HSL {
+    fn from(value: HSV) -> Self {
+        value.to_hsl()
+    }
+}
+
+impl From<Color> for HSV {
+    fn from(value: Color) -> Self {
+        value.to_hsv()
+    }
+}
+
+impl From<HSL> for HSV {
+    fn from(value: HSL) -> Self {
+        value.to_hsv()
+    }
+}
+
+impl From<Color> for Color32 {
+    fn from(value: Color) -> Self {
+        value.to_color32()
+    }
+}
+
+impl From<[u8; 3]> for Color {
+    fn from([r, g, b]: [u8; 3]) -> Self {
+        Color::new(r, g, b)
+    }
+}
+
+impl From<[u8; 4]> for Color {
+    fn from([r, g, b, a]: [u8; 4]) -> Self {
+        Color::new_rgba(r, g, b, a)
+    }
+}
+
+impl From<Color> for [u8; 4] {
+    fn from(value: Color) -> Self {
+        [value.r, value.g, value.b, value.a]
+    }
+}
+
+// [r, g, b] 或 [r, g, b, a]
+impl TryFrom<&[u8]> for Color {
+    type Error = ColorConversionError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        match *value {
+            [r, g, b] => Ok(Color::new(r, g, b)),
+            [r, g, b, a] => Ok(Color::new_rgba(r, g, b, a)),
+            _ => Err(ColorConversionError::InvalidLength(value.len())),
+        }
+    }
+}
+
+// 检查 h 为有限值，其余分量在 0~1 内
+fn check_components(h: f32, rest: &[f32]) -> Result<(), ColorConversionError> {
+    if h.is_finite() && rest.iter().all(|x| (0.0..=1.0).contains(x)) {
+        Ok(())
+    } else {
+        Err(ColorConversionError::OutOfRange)
+    }
+}
+
+// [h, s, l] 或 [h, s, l, a]
+impl TryFrom<&[f32]> for HSL {
+    type Error = ColorConversionError;
+
+    fn try_from(value: &[f32]) -> Result<Self, Self::Error> {
+        let hsl = match *value {
+            [h, s, l] => HSL::new(h, s, l),
+            [h, s, l, a] => HSL { h, s, l, a },
+            _ => return Err(ColorConversionError::InvalidLength(value.len())),
+        };
+        check_components(hsl.h, &[hsl.s, hsl.l, hsl.a])?;
+        Ok(HSL {
+            h: hsl.h.rem_euclid(360.0),
+            ..hsl
+        })
+    }
+}
+
+// [h, s, v] 或 [h, s, v, a]
+impl TryFrom<&[f32]> for HSV {
+    type Error = ColorConversionError;
+
+    fn try_from(value: &[f32]) -> Result<Self, Self::Error> {
+        let hsv = match *value {
+            [h, s, v] => HSV::new(h, s, v),
+            [h, s, v, a] => HSV { h, s, v, a },
+            _ => return Err(ColorConversionError::InvalidLength(value.len())),
+        };
+        check_components(hsv.h, &[hsv.s, hsv.v, hsv.a])?;
+        Ok(HSV {
+            h: hsv.h.rem_euclid(360.0),
+            ..hsv
+        })
+    }
 }
@@ -0,0 +1,137 @@
+use crate::model::color::{Color, difference::delta_e_ok};
+
+// xterm 默认的 16 色，终端主题通常会改掉它们
+pub const ANSI_16: [[u8; 3]; 16] = [
+    [0x00, 0x00, 0x00],
+    [0xCD, 0x00, 0x00],
+    [0x00, 0xCD, 0x00],
+    [0xCD, 0xCD, 0x00],
+    [0x00, 0x00, 0xEE],
+    [0xCD, 0x00, 0xCD],
+    [0x00, 0xCD, 0xCD],
+    [0xE5, 0xE5, 0xE5],
+    [0x7F, 0x7F, 0x7F],
+    [0xFF, 0x00, 0x00],
+    [0x00, 0xFF, 0x00],
+    [0xFF, 0xFF, 0x00],
+    [0x5C, 0x5C, 0xFF],
+    [0xFF, 0x00, 0xFF],
+    [0x00, 0xFF, 0xFF],
+    [0xFF, 0xFF, 0xFF],
+];
+
+pub const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+// 6×6×6 色块每个通道的取值
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// xterm 256 色：0~15 为 ANSI 16 色，16~231 为色块，232~255 为 24 级灰
+pub fn xterm_color(index: u8) -> Color {
+    match index {
+        0..16 => Color::from(ANSI_16[index as usize]),
+        16..232 => {
+            let i = (index - 16) as usize;
+            Color::new(
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[i / 6 % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            Color::new(gray, gray, gray)
+        }
+    }
+}
+
+// 在 indices 中按 ΔEOK 找最接近的颜色，不考虑不透明度
+fn nearest(color: &Color, indices: impl Iterator<Item = u8>) -> u8 {
+    let target = color.to_oklab();
+    indices
+        .map(|i| (i, delta_e_ok(&target, &xterm_color(i).to_oklab())))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+impl Color {
+    // 只在 16~255 中找，前 16 色随终端主题变化
+    pub fn nearest_xterm256(&self) -> u8 {
+        nearest(self, 16..=255)
+    }
+
+    pub fn nearest_ansi16(&self) -> u8 {
+        nearest(self, 0..16)
+    }
+}
+
+// 前景色的转义序列，如 \x1b[38;5;196m
+pub fn xterm256_escape(index: u8) -> String {
+    format!("\\x1b[38;5;{}m", index)
+}
+
+// 0~7 为 30~37，8~15 为高亮的 90~97
+pub fn ansi16_escape(index: u8) -> String {
+    let code = if index < 8 { 30 + index } else { 82 + index };
+    format!("\\x1b[{}m", code)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::color::{
+        Color,
+        ansi::{ansi16_escape, xterm_color, xterm256_escape},
+    };
+
+    #[test]
+    fn xterm_palette_layout() {
+        assert_eq!(xterm_color(1), Color::new(0xCD, 0, 0));
+        assert_eq!(xterm_color(15), Color::new(0xFF, 0xFF, 0xFF));
+        // 色块的顺序为 r、g、b
+        assert_eq!(xterm_color(16), Color::new(0, 0, 0));
+        assert_eq!(xterm_color(17), Color::new(0, 0, 95));
+        assert_eq!(xterm_color(22), Color::new(0, 95, 0));
+        assert_eq!(xterm_color(52), Color::new(95, 0, 0));
+        assert_eq!(xterm_color(196), Color::new(255, 0, 0));
+        assert_eq!(xterm_color(231), Color::new(255, 255, 255));
+        // 灰阶从 8 到 238，步长 10
+        assert_eq!(xterm_color(232), Color::new(8, 8, 8));
+        assert_eq!(xterm_color(244), Color::new(128, 128, 128));
+        assert_eq!(xterm_color(255), Color::new(238, 238, 238));
+    }
+
+    #[test]
+    fn nearest_round_trips_palette_entries() {
+        // 调色板中的颜色找回自己
+        for index in 16..=255 {
+            assert_eq!(xterm_color(index).nearest_xterm256(), index);
+        }
+        for index in 0..16 {
+            assert_eq!(xterm_color(index).nearest_ansi16(), index);
+        }
+    }
+
+    #[test]
+    fn nearest_ignores_the_first_sixteen_and_alpha() {
+        // #CD0000 是 ANSI 1，但 256 色只在 16 之后找
+        assert_eq!(Color::new(0xCD, 0, 0).nearest_xterm256(), 160);
+        assert_eq!(Color::new(0xFA, 0x10, 0x08).nearest_xterm256(), 196);
+        assert_eq!(Color::new(0x80, 0x80, 0x80).nearest_xterm256(), 244);
+        assert_eq!(Color::new(0x81, 0x81, 0x81).nearest_xterm256(), 244);
+        assert_eq!(Color::new_rgba(0xFA, 0x10, 0x08, 0).nearest_xterm256(), 196);
+        assert_eq!(Color::new(0xF0, 0x10, 0x10).nearest_ansi16(), 9);
+        assert_eq!(Color::new(0x10, 0x10, 0x10).nearest_ansi16(), 0);
+        assert_eq!(Color::new(0x70, 0x70, 0x70).nearest_ansi16(), 8);
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(xterm256_escape(196), "\\x1b[38;5;196m");
+        assert_eq!(ansi16_escape(0), "\\x1b[30m");
+        assert_eq!(ansi16_escape(7), "\\x1b[37m");
+        assert_eq!(ansi16_escape(8), "\\x1b[90m");
+        assert_eq!(ansi16_escape(15), "\\x1b[97m");
+    }
+}
@@ -0,0 +1,528 @@
+use crate::model::color::Color;
+
+// 参考白点，XYZ 以 Y = 1 归一化
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WhitePoint {
+    #[default]
+    D65, // sRGB 的白点
+    D50, // ICC 和印刷使用的白点
+}
+
+impl WhitePoint {
+    pub fn xyz(&self) -> [f32; 3] {
+        match self {
+            WhitePoint::D65 => [0.95047, 1.0, 1.08883],
+            WhitePoint::D50 => [0.96422, 1.0, 0.82521],
+        }
+    }
+}
+
+// CIE 1931 XYZ，各分量以 Y = 1 为参考白的亮度
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct XYZ {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub alpha: f32, // 0..=1
+    pub white: WhitePoint,
+}
+
+// CIE L*a*b*
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Lab {
+    pub l: f32, // 0..=100
+    pub a: f32, // 绿 (-) ~ 红 (+)
+    pub b: f32, // 蓝 (-) ~ 黄 (+)
+    pub alpha: f32,
+    pub white: WhitePoint,
+}
+
+// L*a*b* 的柱坐标形式 LCh(ab)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct LCh {
+    pub l: f32, // 0..=100
+    pub c: f32, // 色度，>= 0
+    pub h: f32, // 色相 (0°~360°)
+    pub alpha: f32,
+    pub white: WhitePoint,
+}
+
+const SRGB_TO_XYZ: [[f32; 3]; 3] = [
+    [0.4124564, 0.3575761, 0.1804375],
+    [0.2126729, 0.7151522, 0.0721750],
+    [0.0193339, 0.119192, 0.9503041],
+];
+
+const XYZ_TO_SRGB: [[f32; 3]; 3] = [
+    [3.2404542, -1.5371385, -0.4985314],
+    [-0.969266, 1.8760108, 0.041556],
+    [0.0556434, -0.2040259, 1.0572252],
+];
+
+// Bradford 色适应变换，XYZ 到锥体响应空间
+const BRADFORD: [[f32; 3]; 3] = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+const BRADFORD_INVERSE: [[f32; 3]; 3] = [
+    [0.9869929, -0.1470543, 0.1599627],
+    [0.4323053, 0.5183603, 0.0492912],
+    [-0.0085287, 0.0400428, 0.9684867],
+];
+
+const EPSILON: f32 = 216.0 / 24389.0;
+const KAPPA: f32 = 24389.0 / 27.0;
+
+pub(super) fn mul(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+// sRGB 的 gamma 解码，0~255 到线性光 0~1
+pub fn srgb_to_linear(value: u8) -> f32 {
+    let v = value as f32 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+// sRGB 的 gamma 编码，超出色域的值会被钳制
+pub fn linear_to_srgb(value: f32) -> u8 {
+    let v = value.clamp(0.0, 1.0);
+    let v = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (v * 255.0).round() as u8
+}
+
+pub(crate) fn alpha_to_u8(alpha: f32) -> u8 {
+    (alpha.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+// 半透明时追加不透明度，和 HSL/HSV 的格式一致
+fn alpha_suffix(alpha: f32) -> String {
+    if alpha < 1.0 {
+        format!(" a:{:.2}", alpha)
+    } else {
+        String::new()
+    }
+}
+
+// D50 的读数标出白点，解析时据此还原
+fn white_suffix(white: WhitePoint) -> &'static str {
+    match white {
+        WhitePoint::D65 => "",
+        WhitePoint::D50 => " D50",
+    }
+}
+
+// 避免灰色的 a/b 显示成 -0.0
+fn no_negative_zero(value: f32) -> f32 {
+    if value.abs() < 0.05 { 0.0 } else { value }
+}
+
+impl Color {
+    pub fn to_xyz(&self) -> XYZ {
+        let [x, y, z] = mul(
+            &SRGB_TO_XYZ,
+            [
+                srgb_to_linear(self.r),
+                srgb_to_linear(self.g),
+                srgb_to_linear(self.b),
+            ],
+        );
+        XYZ {
+            x,
+            y,
+            z,
+            alpha: self.alpha(),
+            white: WhitePoint::D65,
+        }
+    }
+
+    pub fn to_lab(&self, white: WhitePoint) -> Lab {
+        self.to_xyz().adapt(white).to_lab()
+    }
+
+    pub fn to_lch(&self, white: WhitePoint) -> LCh {
+        self.to_lab(white).to_lch()
+    }
+}
+
+impl XYZ {
+    pub fn new(x: f32, y: f32, z: f32, white: WhitePoint) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            alpha: 1.0,
+            white,
+        }
+    }
+
+    // 用 Bradford 变换把颜色适应到另一个白点
+    pub fn adapt(&self, white: WhitePoint) -> XYZ {
+        if white == self.white {
+            return *self;
+        }
+        let [src_l, src_m, src_s] = mul(&BRADFORD, self.white.xyz());
+        let [dst_l, dst_m, dst_s] = mul(&BRADFORD, white.xyz());
+        let [l, m, s] = mul(&BRADFORD, [self.x, self.y, self.z]);
+        let [x, y, z] = mul(
+            &BRADFORD_INVERSE,
+            [l * dst_l / src_l, m * dst_m / src_m, s * dst_s / src_s],
+        );
+        XYZ {
+            x,
+            y,
+            z,
+            alpha: self.alpha,
+            white,
+        }
+    }
+
+    pub fn to_rgb(&self) -> Color {
+        let xyz = self.adapt(WhitePoint::D65);
+        let [r, g, b] = mul(&XYZ_TO_SRGB, [xyz.x, xyz.y, xyz.z]);
+        Color::new_rgba(
+            linear_to_srgb(r),
+            linear_to_srgb(g),
+            linear_to_srgb(b),
+            alpha_to_u8(self.alpha),
+        )
+    }
+
+    pub fn to_lab(&self) -> Lab {
+        let [wx, wy, wz] = self.white.xyz();
+        let f = |t: f32| {
+            if t > EPSILON {
+                t.cbrt()
+            } else {
+                (KAPPA * t + 16.0) / 116.0
+            }
+        };
+        let fx = f(self.x / wx);
+        let fy = f(self.y / wy);
+        let fz = f(self.z / wz);
+
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+            alpha: self.alpha,
+            white: self.white,
+        }
+    }
+}
+
+impl Lab {
+    pub fn new(l: f32, a: f32, b: f32, white: WhitePoint) -> Self {
+        Self {
+            l,
+            a,
+            b,
+            alpha: 1.0,
+            white,
+        }
+    }
+
+    pub fn to_xyz(&self) -> XYZ {
+        let [wx, wy, wz] = self.white.xyz();
+        let fy = (self.l + 16.0) / 116.0;
+        let fx = fy + self.a / 500.0;
+        let fz = fy - self.b / 200.0;
+        let f_inverse = |t: f32| {
+            if t.powi(3) > EPSILON {
+                t.powi(3)
+            } else {
+                (116.0 * t - 16.0) / KAPPA
+            }
+        };
+        let y = if self.l > KAPPA * EPSILON {
+            fy.powi(3)
+        } else {
+            self.l / KAPPA
+        };
+
+        XYZ {
+            x: f_inverse(fx) * wx,
+            y: y * wy,
+            z: f_inverse(fz) * wz,
+            alpha: self.alpha,
+            white: self.white,
+        }
+    }
+
+    pub fn to_rgb(&self) -> Color {
+        self.to_xyz().to_rgb()
+    }
+
+    pub fn to_lch(&self) -> LCh {
+        let c = self.a.hypot(self.b);
+        // 灰色没有色相，和 HSL/HSV 一样记为 0
+        let h = if c < 1e-4 {
+            0.0
+        } else {
+            self.b.atan2(self.a).to_degrees().rem_euclid(360.0)
+        };
+
+        LCh {
+            l: self.l,
+            c,
+            h,
+            alpha: self.alpha,
+            white: self.white,
+        }
+    }
+}
+
+impl LCh {
+    pub fn new(l: f32, c: f32, h: f32, white: WhitePoint) -> Self {
+        Self {
+            l,
+            c,
+            h,
+            alpha: 1.0,
+            white,
+        }
+    }
+
+    pub fn to_lab(&self) -> Lab {
+        let h = self.h.to_radians();
+        Lab {
+            l: self.l,
+            a: self.c * h.cos(),
+            b: self.c * h.sin(),
+            alpha: self.alpha,
+            white: self.white,
+        }
+    }
+
+    pub fn to_rgb(&self) -> Color {
+        self.to_lab().to_rgb()
+    }
+}
+
+impl From<XYZ> for String {
+    fn from(value: XYZ) -> Self {
+        // 按惯例以 Y = 100 显示
+        format!(
+            "x:{:.1} y:{:.1} z:{:.1}{}",
+            value.x * 100.0,
+            value.y * 100.0,
+            value.z * 100.0,
+            alpha_suffix(value.alpha)
+        )
+    }
+}
+
+impl From<Lab> for String {
+    fn from(value: Lab) -> Self {
+        format!(
+            "L:{:.1} a:{:.1} b:{:.1}{}{}",
+            value.l,
+            no_negative_zero(value.a),
+            no_negative_zero(value.b),
+            alpha_suffix(value.alpha),
+            white_suffix(value.white)
+        )
+    }
+}
+
+impl From<LCh> for String {
+    fn from(value: LCh) -> Self {
+        format!(
+            "L:{:.1} C:{:.1} h:{:.1}{}{}",
+            value.l,
+            value.c,
+            value.h,
+            alpha_suffix(value.alpha),
+            white_suffix(value.white)
+        )
+    }
+}
+
+impl From<Color> for XYZ {
+    fn from(value: Color) -> Self {
+        value.to_xyz()
+    }
+}
+
+impl From<XYZ> for Color {
+    fn from(value: XYZ) -> Self {
+        value.to_rgb()
+    }
+}
+
+impl From<XYZ> for Lab {
+    fn from(value: XYZ) -> Self {
+        value.to_lab()
+    }
+}
+
+impl From<Lab> for XYZ {
+    fn from(value: Lab) -> Self {
+        value.to_xyz()
+    }
+}
+
+impl From<Lab> for Color {
+    fn from(value: Lab) -> Self {
+        value.to_rgb()
+    }
+}
+
+impl From<Lab> for LCh {
+    fn from(value: Lab) -> Self {
+        value.to_lch()
+    }
+}
+
+impl From<LCh> for Lab {
+    fn from(value: LCh) -> Self {
+        value.to_lab()
+    }
+}
+
+impl From<LCh> for Color {
+    fn from(value: LCh) -> Self {
+        value.to_rgb()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::color::{
+        Color,
+        cie::{LCh, Lab, WhitePoint, XYZ},
+    };
+
+    fn assert_near(actual: [f32; 3], expected: [f32; 3], tolerance: f32) {
+        for (a, e) in actual.into_iter().zip(expected) {
+            assert!(
+                (a - e).abs() <= tolerance,
+                "{:?} is not within {} of {:?}",
+                actual,
+                tolerance,
+                expected
+            );
+        }
+    }
+
+    fn xyz(value: XYZ) -> [f32; 3] {
+        [value.x, value.y, value.z]
+    }
+
+    fn lab(value: Lab) -> [f32; 3] {
+        [value.l, value.a, value.b]
+    }
+
+    #[test]
+    fn white_is_the_d65_white_point() {
+        let white = Color::new(255, 255, 255).to_xyz();
+        assert_eq!(white.white, WhitePoint::D65);
+        assert_near(xyz(white), WhitePoint::D65.xyz(), 1e-4);
+        assert_near(
+            lab(Color::new(255, 255, 255).to_lab(WhitePoint::D65)),
+            [100.0, 0.0, 0.0],
+            1e-2,
+        );
+    }
+
+    // 参考值来自 Bruce Lindbloom 的 sRGB 矩阵和计算器
+    #[test]
+    fn srgb_reference_values() {
+        let red = Color::new(255, 0, 0);
+        assert_near(xyz(red.to_xyz()), [0.4124564, 0.2126729, 0.0193339], 1e-5);
+        assert_near(
+            lab(red.to_lab(WhitePoint::D65)),
+            [53.2408, 80.0925, 67.2032],
+            1e-2,
+        );
+        let lch = red.to_lch(WhitePoint::D65);
+        assert_near([lch.l, lch.c, lch.h], [53.2408, 104.5518, 39.999], 1e-2);
+
+        assert_near(
+            lab(Color::new(0, 0, 255).to_lab(WhitePoint::D65)),
+            [32.2970, 79.1875, -107.8602],
+            1e-2,
+        );
+    }
+
+    // D50 的值经 Bradford 适应，和 Lindbloom 的 sRGB D50 矩阵一致
+    #[test]
+    fn bradford_adapted_d50_values() {
+        let red = Color::new(255, 0, 0);
+        assert_near(
+            xyz(red.to_xyz().adapt(WhitePoint::D50)),
+            [0.4360747, 0.2225045, 0.0139322],
+            1e-4,
+        );
+        assert_near(
+            xyz(Color::new(255, 255, 255).to_xyz().adapt(WhitePoint::D50)),
+            WhitePoint::D50.xyz(),
+            1e-4,
+        );
+        assert_near(
+            lab(red.to_lab(WhitePoint::D50)),
+            [54.2917, 80.8125, 69.8851],
+            2e-2,
+        );
+    }
+
+    #[test]
+    fn round_trips_through_lab_and_xyz() {
+        for white in [WhitePoint::D65, WhitePoint::D50] {
+            for value in (0..=255).step_by(15) {
+                for color in [
+                    Color::new(value, 0, 0),
+                    Color::new(0, value, 255 - value),
+                    Color::new(value, value, value),
+                    Color::new_rgba(255 - value, 128, value, value),
+                ] {
+                    let lab = color.to_lab(white);
+                    assert_eq!(lab.to_rgb(), color, "{:?}", white);
+                    assert_eq!(lab.to_xyz().to_rgb(), color, "{:?}", white);
+                    assert_eq!(lab.to_xyz().to_lab().to_rgb(), color, "{:?}", white);
+                    assert_eq!(lab.to_lch().to_lab().to_rgb(), color, "{:?}", white);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gray_has_no_negative_zero_or_hue() {
+        for value in [0, 1, 64, 128, 200, 255] {
+            let gray = Color::new(value, value, value);
+            for white in [WhitePoint::D65, WhitePoint::D50] {
+                let text: String = gray.to_lab(white).into();
+                assert!(!text.contains("-0.0"), "{}", text);
+                assert!(text.contains(" a:0.0 b:0.0"), "{}", text);
+                assert_eq!(gray.to_lch(white).h, 0.0);
+            }
+        }
+        let text: String = Color::new(128, 128, 128).to_lab(WhitePoint::D65).into();
+        assert_eq!(text, "L:53.6 a:0.0 b:0.0");
+    }
+
+    #[test]
+    fn display_formats() {
+        let text: String = Color::new(255, 255, 255).to_xyz().into();
+        assert_eq!(text, "x:95.0 y:100.0 z:108.9");
+        let text: String = LCh::new(50.0, 20.0, 120.0, WhitePoint::D65).into();
+        assert_eq!(text, "L:50.0 C:20.0 h:120.0");
+        let mut lab = Lab::new(50.0, -20.0, 10.0, WhitePoint::D50);
+        lab.alpha = 0.5;
+        let text: String = lab.into();
+        assert_eq!(text, "L:50.0 a:-20.0 b:10.0 a:0.50 D50");
+    }
+}
@@ -0,0 +1,173 @@
+use crate::model::color::{Color, cie::srgb_to_linear};
+
+// WCAG 2.x 的对比度，忽略不透明度
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct WcagContrast {
+    pub ratio: f32, // 1..=21
+}
+
+impl WcagContrast {
+    // 普通文本 AA 4.5:1，大号文本 3:1
+    pub fn aa_normal(&self) -> bool {
+        self.ratio >= 4.5
+    }
+
+    pub fn aa_large(&self) -> bool {
+        self.ratio >= 3.0
+    }
+
+    // 普通文本 AAA 7:1，大号文本 4.5:1
+    pub fn aaa_normal(&self) -> bool {
+        self.ratio >= 7.0
+    }
+
+    pub fn aaa_large(&self) -> bool {
+        self.ratio >= 4.5
+    }
+}
+
+impl Color {
+    // WCAG 的相对亮度，0 为黑，1 为白
+    pub fn relative_luminance(&self) -> f32 {
+        0.2126 * srgb_to_linear(self.r)
+            + 0.7152 * srgb_to_linear(self.g)
+            + 0.0722 * srgb_to_linear(self.b)
+    }
+
+    // 和另一个颜色的对比度，与前后景顺序无关
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let l1 = self.relative_luminance();
+        let l2 = other.relative_luminance();
+        (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+    }
+
+    pub fn wcag_contrast(&self, other: &Color) -> WcagContrast {
+        WcagContrast {
+            ratio: self.contrast_ratio(other),
+        }
+    }
+
+    // APCA 0.0.98G-4g 的 Lc 值，self 为文字色
+    // 深色文字在浅色背景上为正，浅色文字在深色背景上为负，约 -108..=106
+    pub fn apca_contrast(&self, background: &Color) -> f32 {
+        let text = apca_luminance(self);
+        let background = apca_luminance(background);
+        if (background - text).abs() < 0.0005 {
+            return 0.0;
+        }
+
+        if background > text {
+            let sapc = (background.powf(0.56) - text.powf(0.57)) * 1.14;
+            if sapc < 0.1 {
+                0.0
+            } else {
+                (sapc - 0.027) * 100.0
+            }
+        } else {
+            let sapc = (background.powf(0.65) - text.powf(0.62)) * 1.14;
+            if sapc > -0.1 {
+                0.0
+            } else {
+                (sapc + 0.027) * 100.0
+            }
+        }
+    }
+}
+
+// APCA 使用简单的 2.4 次幂，并对接近黑色的值做软钳制
+fn apca_luminance(color: &Color) -> f32 {
+    let channel = |value: u8| (value as f32 / 255.0).powf(2.4);
+    let y =
+        0.2126729 * channel(color.r) + 0.7151522 * channel(color.g) + 0.0721750 * channel(color.b);
+    if y < 0.022 {
+        y + (0.022 - y).powf(1.414)
+    } else {
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::color::{Color, contrast::WcagContrast};
+
+    fn gray(value: u8) -> Color {
+        Color::new(value, value, value)
+    }
+
+    #[test]
+    fn wcag_ratio() {
+        let black = gray(0);
+        let white = gray(255);
+        assert!((black.contrast_ratio(&white) - 21.0).abs() < 1e-4);
+        assert!((white.contrast_ratio(&black) - 21.0).abs() < 1e-4);
+        assert_eq!(white.contrast_ratio(&white), 1.0);
+        // 不透明度不影响对比度
+        assert_eq!(
+            Color::new_rgba(0, 0, 0, 0).contrast_ratio(&white),
+            black.contrast_ratio(&white)
+        );
+    }
+
+    #[test]
+    fn wcag_levels_at_the_boundaries() {
+        let at = |ratio| WcagContrast { ratio };
+        assert!(at(4.5).aa_normal() && at(4.5).aaa_large());
+        assert!(!at(4.49).aa_normal() && !at(4.49).aaa_large());
+        assert!(at(3.0).aa_large() && !at(2.99).aa_large());
+        assert!(at(7.0).aaa_normal() && !at(6.99).aaa_normal());
+
+        // 白底上刚好通过和刚好不通过的灰色
+        let white = gray(255);
+        assert!(gray(0x76).wcag_contrast(&white).aa_normal());
+        assert!(!gray(0x77).wcag_contrast(&white).aa_normal());
+        assert!(gray(0x59).wcag_contrast(&white).aaa_normal());
+        assert!(!gray(0x5a).wcag_contrast(&white).aaa_normal());
+        assert!(gray(0x94).wcag_contrast(&white).aa_large());
+        assert!(!gray(0x95).wcag_contrast(&white).aa_large());
+    }
+
+    // APCA 0.0.98G-4g 参考实现 (apca-w3) 的值
+    #[test]
+    fn apca_reference_values() {
+        for (text, background, lc) in [
+            (gray(0x88), gray(0xff), 63.056),
+            (gray(0xff), gray(0x88), -68.541),
+            (gray(0x00), gray(0xaa), 58.146),
+            (gray(0xaa), gray(0x00), -56.243),
+            (
+                Color::new(0x11, 0x22, 0x33),
+                Color::new(0xdd, 0xee, 0xff),
+                91.663,
+            ),
+            (
+                Color::new(0xdd, 0xee, 0xff),
+                Color::new(0x11, 0x22, 0x33),
+                -93.066,
+            ),
+            (gray(0x00), gray(0xff), 106.04),
+            (gray(0xff), gray(0x00), -107.88),
+        ] {
+            let actual = text.apca_contrast(&background);
+            assert!(
+                (actual - lc).abs() < 0.05,
+                "{:?} on {:?}: {} != {}",
+                text,
+                background,
+                actual,
+                lc
+            );
+        }
+    }
+
+    #[test]
+    fn apca_polarity_and_low_contrast() {
+        let dark = gray(0x33);
+        let light = gray(0xee);
+        assert!(dark.apca_contrast(&light) > 0.0);
+        assert!(light.apca_contrast(&dark) < 0.0);
+        assert_eq!(light.apca_contrast(&light), 0.0);
+        // 差别很小时钳制为 0
+        assert_eq!(gray(0x80).apca_contrast(&gray(0x84)), 0.0);
+        assert_eq!(gray(0x84).apca_contrast(&gray(0x80)), 0.0);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::model::color::{Color, cie::Lab, cie::WhitePoint, oklab::OKLab};
+
+// 色差公式，Lab 类公式在 D65 的 L*a*b* 上计算
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaE {
+    E76,
+    E94,
+    #[default]
+    E2000,
+    OK,
+}
+
+impl DeltaE {
+    pub const ALL: [DeltaE; 4] = [DeltaE::E76, DeltaE::E94, DeltaE::E2000, DeltaE::OK];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeltaE::E76 => "ΔE76",
+            DeltaE::E94 => "ΔE94",
+            DeltaE::E2000 => "ΔE00",
+            DeltaE::OK => "ΔEOK",
+        }
+    }
+
+    // reference 为参考色，CIE94 不对称，以参考色的色度加权
+    pub fn between(&self, reference: &Color, sample: &Color) -> f32 {
+        match self {
+            DeltaE::E76 => delta_e76(
+                &reference.to_lab(WhitePoint::D65),
+                &sample.to_lab(WhitePoint::D65),
+            ),
+            DeltaE::E94 => delta_e94(
+                &reference.to_lab(WhitePoint::D65),
+                &sample.to_lab(WhitePoint::D65),
+            ),
+            DeltaE::E2000 => delta_e2000(
+                &reference.to_lab(WhitePoint::D65),
+                &sample.to_lab(WhitePoint::D65),
+            ),
+            DeltaE::OK => delta_e_ok(&reference.to_oklab(), &sample.to_oklab()),
+        }
+    }
+
+    // 下一个公式，用于按钮循环切换
+    pub fn next(&self) -> DeltaE {
+        let index = DeltaE::ALL.iter().position(|d| d == self).unwrap_or(0);
+        DeltaE::ALL[(index + 1) % DeltaE::ALL.len()]
+    }
+}
+
+// CIE76，L*a*b* 上的欧氏距离
+pub fn delta_e76(lab1: &Lab, lab2: &Lab) -> f32 {
+    ((lab1.l - lab2.l).powi(2) + (lab1.a - lab2.a).powi(2) + (lab1.b - lab2.b).powi(2)).sqrt()
+}
+
+// CIE94，图形艺术的参数 kL = 1, K1 = 0.045, K2 = 0.015
+pub fn delta_e94(reference: &Lab, sample: &Lab) -> f32 {
+    let (l1, a1, b1) = (reference.l as f64, reference.a as f64, reference.b as f64);
+    let (l2, a2, b2) = (sample.l as f64, sample.a as f64, sample.b as f64);
+
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+    let dl = l1 - l2;
+    let dc = c1 - c2;
+    let dh2 = ((a1 - a2).powi(2) + (b1 - b2).powi(2) - dc.powi(2)).max(0.0);
+
+    let sc = 1.0 + 0.045 * c1;
+    let sh = 1.0 + 0.015 * c1;
+    (dl.powi(2) + (dc / sc).powi(2) + dh2 / sh.powi(2)).sqrt() as f32
+}
+
+// CIEDE2000，参数 kL = kC = kH = 1
+// 按 Sharma, Wu, Dalal (2005) 的实现说明计算，用 f64 保证精度
+pub fn delta_e2000(lab1: &Lab, lab2: &Lab) -> f32 {
+    let (l1, a1, b1) = (lab1.l as f64, lab1.a as f64, lab1.b as f64);
+    let (l2, a2, b2) = (lab2.l as f64, lab2.a as f64, lab2.b as f64);
+    let pow25_7 = 25f64.powi(7);
+
+    let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt());
+    let a1 = (1.0 + g) * a1;
+    let a2 = (1.0 + g) * a2;
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_mean).to_radians().cos()
+        + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+    let d_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let rc = 2.0 * (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt();
+    let sl = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * c_mean;
+    let sh = 1.0 + 0.015 * c_mean * t;
+    let rt = -(2.0 * d_theta).to_radians().sin() * rc;
+
+    ((dl / sl).powi(2) + (dc / sc).powi(2) + (dh / sh).powi(2) + rt * (dc / sc) * (dh / sh)).sqrt()
+        as f32
+}
+
+// OKLab 上的欧氏距离，即 CSS Color 4 的 deltaEOK
+pub fn delta_e_ok(lab1: &OKLab, lab2: &OKLab) -> f32 {
+    ((lab1.l - lab2.l).powi(2) + (lab1.a - lab2.a).powi(2) + (lab1.b - lab2.b).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::color::{
+        cie::{Lab, WhitePoint},
+        difference::delta_e2000,
+    };
+
+    // Sharma, Wu, Dalal (2005) 表 1 的 34 组数据：L1 a1 b1, L2 a2 b2, ΔE00
+    #[rustfmt::skip]
+    const SHARMA: [([f32; 3], [f32; 3], f32); 34] = [
+        ([50.0000, 2.6772, -79.7751], [50.0000, 0.0000, -82.7485], 2.0425),
+        ([50.0000, 3.1571, -77.2803], [50.0000, 0.0000, -82.7485], 2.8615),
+        ([50.0000, 2.8361, -74.0200], [50.0000, 0.0000, -82.7485], 3.4412),
+        ([50.0000, -1.3802, -84.2814], [50.0000, 0.0000, -82.7485], 1.0000),
+        ([50.0000, -1.1848, -84.8006], [50.0000, 0.0000, -82.7485], 1.0000),
+        ([50.0000, -0.9009, -85.5211], [50.0000, 0.0000, -82.7485], 1.0000),
+        ([50.0000, 0.0000, 0.0000], [50.0000, -1.0000, 2.0000], 2.3669),
+        ([50.0000, -1.0000, 2.0000], [50.0000, 0.0000, 0.0000], 2.3669),
+        ([50.0000, 2.4900, -0.0010], [50.0000, -2.4900, 0.0009], 7.1792),
+        ([50.0000, 2.4900, -0.0010], [50.0000, -2.4900, 0.0010], 7.1792),
+        ([50.0000, 2.4900, -0.0010], [50.0000, -2.4900, 0.0011], 7.2195),
+        ([50.0000, 2.4900, -0.0010], [50.0000, -2.4900, 0.0012], 7.2195),
+        ([50.0000, -0.0010, 2.4900], [50.0000, 0.0009, -2.4900], 4.8045),
+        ([50.0000, -0.0010, 2.4900], [50.0000, 0.0010, -2.4900], 4.8045),
+        ([50.0000, -0.0010, 2.4900], [50.0000, 0.0011, -2.4900], 4.7461),
+        ([50.0000, 2.5000, 0.0000], [50.0000, 0.0000, -2.5000], 4.3065),
+        ([50.0000, 2.5000, 0.0000], [73.0000, 25.0000, -18.0000], 27.1492),
+        ([50.0000, 2.5000, 0.0000], [61.0000, -5.0000, 29.0000], 22.8977),
+        ([50.0000, 2.5000, 0.0000], [56.0000, -27.0000, -3.0000], 31.9030),
+        ([50.0000, 2.5000, 0.0000], [58.0000, 24.0000, 15.0000], 19.4535),
+        ([50.0000, 2.5000, 0.0000], [50.0000, 3.1736, 0.5854], 1.0000),
+        ([50.0000, 2.5000, 0.0000], [50.0000, 3.2972, 0.0000], 1.0000),
+        ([50.0000, 2.5000, 0.0000], [50.0000, 1.8634, 0.5757], 1.0000),
+        ([50.0000, 2.5000, 0.0000], [50.0000, 3.2592, 0.3350], 1.0000),
+        ([60.2574, -34.0099, 36.2677], [60.4626, -34.1751, 39.4387], 1.2644),
+        ([63.0109, -31.0961, -5.8663], [62.8187, -29.7946, -4.0864], 1.2630),
+        ([61.2901, 3.7196, -5.3901], [61.4292, 2.2480, -4.9620], 1.8731),
+        ([35.0831, -44.1164, 3.7933], [35.0232, -40.0716, 1.5901], 1.8645),
+        ([22.7233, 20.0904, -46.6940], [23.0331, 14.9730, -42.5619], 2.0373),
+        ([36.4612, 47.8580, 18.3852], [36.2715, 50.5065, 21.2231], 1.4146),
+        ([90.8027, -2.0831, 1.4410], [91.1528, -1.6435, 0.0447], 1.4441),
+        ([90.9257, -0.5406, -0.9208], [88.6381, -0.8985, -0.7239], 1.5381),
+        ([6.7747, -0.2908, -2.4247], [5.8714, -0.0985, -2.2286], 0.6377),
+        ([2.0776, 0.0795, -1.1350], [0.9033, -0.0636, -0.5514], 0.9082),
+    ];
+
+    #[test]
+    fn matches_sharma_data() {
+        let lab = |[l, a, b]: [f32; 3]| Lab::new(l, a, b, WhitePoint::D65);
+        for (i, (lab1, lab2, expected)) in SHARMA.into_iter().enumerate() {
+            // 公式对两个颜色是对称的
+            for (x, y) in [(lab(lab1), lab(lab2)), (lab(lab2), lab(lab1))] {
+                let actual = delta_e2000(&x, &y);
+                assert!(
+                    (actual - expected).abs() < 1e-4,
+                    "pair {}: {} != {}",
+                    i + 1,
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use crate::model::color::{
+    Color,
+    cie::{alpha_to_u8, linear_to_srgb, mul, srgb_to_linear},
+};
+
+// Björn Ottosson 的 OKLab，感知均匀，CSS Color 4 的 oklab()
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct OKLab {
+    pub l: f32, // 0..=1
+    pub a: f32, // 约 -0.4..=0.4
+    pub b: f32, // 约 -0.4..=0.4
+    pub alpha: f32,
+}
+
+// OKLab 的柱坐标形式，CSS 的 oklch()
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct OKLCh {
+    pub l: f32, // 0..=1
+    pub c: f32, // 色度，>= 0
+    pub h: f32, // 色相 (0°~360°)
+    pub alpha: f32,
+}
+
+// 线性 sRGB 到 LMS
+#[allow(clippy::excessive_precision)]
+const LINEAR_TO_LMS: [[f32; 3]; 3] = [
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005],
+];
+
+#[allow(clippy::excessive_precision)]
+const LMS_TO_OKLAB: [[f32; 3]; 3] = [
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+];
+
+#[allow(clippy::excessive_precision)]
+const OKLAB_TO_LMS: [[f32; 3]; 3] = [
+    [1.0, 0.3963377774, 0.2158037573],
+    [1.0, -0.1055613458, -0.0638541728],
+    [1.0, -0.0894841775, -1.2914855480],
+];
+
+#[allow(clippy::excessive_precision)]
+const LMS_TO_LINEAR: [[f32; 3]; 3] = [
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
+// CSS 的不透明度写法，如 oklch(62.8% 0.258 29.23 / 0.5)
+fn css_alpha(alpha: f32) -> String {
+    if alpha < 1.0 {
+        let a = format!("{:.3}", alpha);
+        format!(" / {}", a.trim_end_matches('0').trim_end_matches('.'))
+    } else {
+        String::new()
+    }
+}
+
+impl Color {
+    pub fn to_oklab(&self) -> OKLab {
+        let [l, m, s] = mul(
+            &LINEAR_TO_LMS,
+            [
+                srgb_to_linear(self.r),
+                srgb_to_linear(self.g),
+                srgb_to_linear(self.b),
+            ],
+        );
+        let [l, a, b] = mul(&LMS_TO_OKLAB, [l.cbrt(), m.cbrt(), s.cbrt()]);
+        OKLab {
+            l,
+            a,
+            b,
+            alpha: self.alpha(),
+        }
+    }
+
+    pub fn to_oklch(&self) -> OKLCh {
+        self.to_oklab().to_oklch()
+    }
+}
+
+impl OKLab {
+    pub fn new(l: f32, a: f32, b: f32) -> Self {
+        Self {
+            l,
+            a,
+            b,
+            alpha: 1.0,
+        }
+    }
+
+    // 超出 sRGB 色域的颜色会被钳制
+    pub fn to_rgb(&self) -> Color {
+        let [l, m, s] = mul(&OKLAB_TO_LMS, [self.l, self.a, self.b]);
+        let [r, g, b] = mul(&LMS_TO_LINEAR, [l.powi(3), m.powi(3), s.powi(3)]);
+        Color::new_rgba(
+            linear_to_srgb(r),
+            linear_to_srgb(g),
+            linear_to_srgb(b),
+            alpha_to_u8(self.alpha),
+        )
+    }
+
+    pub fn to_oklch(&self) -> OKLCh {
+        let c = self.a.hypot(self.b);
+        // 灰色没有色相，记为 0
+        let h = if c < 1e-4 {
+            0.0
+        } else {
+            self.b.atan2(self.a).to_degrees().rem_euclid(360.0)
+        };
+
+        OKLCh {
+            l: self.l,
+            c,
+            h,
+            alpha: self.alpha,
+        }
+    }
+
+    // oklab(62.8% 0.225 0.126)
+    pub fn to_css(&self) -> String {
+        // 加 0.0 避免灰色显示成 -0.000
+        format!(
+            "oklab({:.1}% {:.3} {:.3}{})",
+            self.l * 100.0,
+            (self.a * 1000.0).round() / 1000.0 + 0.0,
+            (self.b * 1000.0).round() / 1000.0 + 0.0,
+            css_alpha(self.alpha)
+        )
+    }
+}
+
+impl OKLCh {
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        Self {
+            l,
+            c,
+            h,
+            alpha: 1.0,
+        }
+    }
+
+    pub fn to_oklab(&self) -> OKLab {
+        let h = self.h.to_radians();
+        OKLab {
+            l: self.l,
+            a: self.c * h.cos(),
+            b: self.c * h.sin(),
+            alpha: self.alpha,
+        }
+    }
+
+    pub fn to_rgb(&self) -> Color {
+        self.to_oklab().to_rgb()
+    }
+
+    // oklch(62.8% 0.258 29.23)
+    pub fn to_css(&self) -> String {
+        format!(
+            "oklch({:.1}% {:.3} {:.2}{})",
+            self.l * 100.0,
+            self.c,
+            self.h,
+            css_alpha(self.alpha)
+        )
+    }
+}
+
+impl From<OKLab> for String {
+    fn from(value: OKLab) -> Self {
+        value.to_css()
+    }
+}
+
+impl From<OKLCh> for String {
+    fn from(value: OKLCh) -> Self {
+        value.to_css()
+    }
+}
+
+impl From<Color> for OKLab {
+    fn from(value: Color) -> Self {
+        value.to_oklab()
+    }
+}
+
+impl From<OKLab> for Color {
+    fn from(value: OKLab) -> Self {
+        value.to_rgb()
+    }
+}
+
+impl From<Color> for OKLCh {
+    fn from(value: Color) -> Self {
+        value.to_oklch()
+    }
+}
+
+impl From<OKLCh> for Color {
+    fn from(value: OKLCh) -> Self {
+        value.to_rgb()
+    }
+}
+
+impl From<OKLab> for OKLCh {
+    fn from(value: OKLab) -> Self {
+        value.to_oklch()
+    }
+}
+
+impl From<OKLCh> for OKLab {
+    fn from(value: OKLCh) -> Self {
+        value.to_oklab()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::color::{
+        Color,
+        oklab::{OKLCh, OKLab},
+    };
+
+    fn assert_near(actual: [f32; 3], expected: [f32; 3], tolerance: f32) {
+        for (a, e) in actual.into_iter().zip(expected) {
+            assert!(
+                (a - e).abs() <= tolerance,
+                "{:?} is not within {} of {:?}",
+                actual,
+                tolerance,
+                expected
+            );
+        }
+    }
+
+    // Ottosson 给出的 sRGB 原色参考值，和 CSS Color 4 一致
+    #[test]
+    fn reference_values() {
+        for (color, expected) in [
+            (Color::new(255, 255, 255), [1.0, 0.0, 0.0]),
+            (Color::new(0, 0, 0), [0.0, 0.0, 0.0]),
+            (Color::new(255, 0, 0), [0.627955, 0.224863, 0.125846]),
+            (Color::new(0, 255, 0), [0.866440, -0.233888, 0.179498]),
+            (Color::new(0, 0, 255), [0.452014, -0.032457, -0.311528]),
+        ] {
+            let oklab = color.to_oklab();
+            assert_near([oklab.l, oklab.a, oklab.b], expected, 1e-4);
+        }
+
+        let red = Color::new(255, 0, 0).to_oklch();
+        assert_near([red.l, red.c, red.h], [0.627955, 0.257683, 29.2339], 1e-3);
+        assert_eq!(red.to_css(), "oklch(62.8% 0.258 29.23)");
+        assert_eq!(
+            Color::new(255, 0, 0).to_oklab().to_css(),
+            "oklab(62.8% 0.225 0.126)"
+        );
+    }
+
+    #[test]
+    fn gray_has_no_hue_or_negative_zero() {
+        for value in [0, 1, 64, 128, 200, 255] {
+            let gray = Color::new(value, value, value);
+            assert_eq!(gray.to_oklch().h, 0.0);
+            assert!(gray.to_oklch().c < 1e-4);
+            let css = gray.to_oklab().to_css();
+            assert!(css.ends_with(" 0.000 0.000)"), "{}", css);
+        }
+        assert_eq!(
+            Color::new(128, 128, 128).to_oklch().to_css(),
+            "oklch(60.0% 0.000 0.00)"
+        );
+    }
+
+    #[test]
+    fn css_alpha() {
+        let half = Color::new_rgba(255, 0, 0, 128);
+        assert!(half.to_oklab().to_css().ends_with(" / 0.502)"));
+        assert!(half.to_oklch().to_css().ends_with(" / 0.502)"));
+
+        let mut oklch = OKLCh::new(0.5, 0.1, 120.0);
+        oklch.alpha = 0.5;
+        assert_eq!(oklch.to_css(), "oklch(50.0% 0.100 120.00 / 0.5)");
+        oklch.alpha = 0.0;
+        assert_eq!(oklch.to_css(), "oklch(50.0% 0.100 120.00 / 0)");
+
+        let mut oklab = OKLab::new(0.5, -0.0001, 0.1);
+        oklab.alpha = 0.25;
+        assert_eq!(oklab.to_css(), "oklab(50.0% 0.000 0.100 / 0.25)");
+    }
+
+    #[test]
+    fn round_trips_through_oklab_and_oklch() {
+        for value in (0..=255).step_by(15) {
+            for color in [
+                Color::new(value, 0, 0),
+                Color::new(0, value, 255 - value),
+                Color::new(value, value, value),
+                Color::new_rgba(255 - value, 128, value, value),
+            ] {
+                assert_eq!(color.to_oklab().to_rgb(), color);
+                assert_eq!(color.to_oklch().to_rgb(), color);
+            }
+        }
+    }
+}
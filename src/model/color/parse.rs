@@ -0,0 +1,738 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::model::{
+    color::{
+        Color, ColorConversionError, HSL, HSV,
+        cie::{LCh, Lab, WhitePoint, XYZ},
+        oklab::{OKLCh, OKLab},
+    },
+    named_color::css::CSS,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseColorError {
+    Empty,
+    InvalidHex(String),
+    UnknownName(String),
+    UnknownFunction(String),
+    UnclosedParenthesis,
+    EmptyComponent(String),
+    ArgumentCount {
+        format: String,
+        expected: usize,
+        found: usize,
+    },
+    InvalidNumber(String),
+    InvalidUnit {
+        component: &'static str,
+        value: String,
+    },
+    OutOfRange {
+        component: &'static str,
+        value: f32,
+    },
+    UnknownLabels(String),
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseColorError::Empty => write!(f, "empty color string"),
+            ParseColorError::InvalidHex(hex) => write!(f, "invalid hex color: {}", hex),
+            ParseColorError::UnknownName(name) => write!(f, "unknown color name: {}", name),
+            ParseColorError::UnknownFunction(name) => {
+                write!(f, "unknown color function: {}()", name)
+            }
+            ParseColorError::UnclosedParenthesis => write!(f, "missing closing parenthesis"),
+            ParseColorError::EmptyComponent(format) => {
+                write!(f, "{} has an empty component", format)
+            }
+            ParseColorError::ArgumentCount {
+                format,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{} expects {} components (plus optional alpha), got {}",
+                format, expected, found
+            ),
+            ParseColorError::InvalidNumber(value) => write!(f, "invalid number: {}", value),
+            ParseColorError::InvalidUnit { component, value } => {
+                write!(f, "invalid unit for {}: {}", component, value)
+            }
+            ParseColorError::OutOfRange { component, value } => {
+                write!(f, "{} out of range: {}", component, value)
+            }
+            ParseColorError::UnknownLabels(labels) => {
+                write!(f, "unknown component labels: {}", labels)
+            }
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+impl From<ColorConversionError> for ParseColorError {
+    fn from(value: ColorConversionError) -> Self {
+        match value {
+            ColorConversionError::InvalidHex(hex) => ParseColorError::InvalidHex(hex),
+            other => ParseColorError::InvalidNumber(other.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    None,
+    Percent,
+    Deg,
+    Rad,
+    Grad,
+    Turn,
+}
+
+// 一个分量，如 50%、120deg、0.5，CSS 的 none 当作 0
+#[derive(Debug, Clone, Copy)]
+struct Component<'a> {
+    text: &'a str,
+    value: f32,
+    unit: Unit,
+}
+
+impl<'a> Component<'a> {
+    fn parse(text: &'a str) -> Result<Self, ParseColorError> {
+        if text.eq_ignore_ascii_case("none") {
+            return Ok(Component {
+                text,
+                value: 0.0,
+                unit: Unit::None,
+            });
+        }
+        let (number, unit) = text.split_at(number_len(text));
+        let unit = match unit.to_ascii_lowercase().as_str() {
+            "" => Unit::None,
+            "%" => Unit::Percent,
+            "deg" => Unit::Deg,
+            "rad" => Unit::Rad,
+            "grad" => Unit::Grad,
+            "turn" => Unit::Turn,
+            _ => return Err(ParseColorError::InvalidNumber(text.to_string())),
+        };
+        let value = number
+            .parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| ParseColorError::InvalidNumber(text.to_string()))?;
+        Ok(Component { text, value, unit })
+    }
+
+    fn invalid_unit(&self, component: &'static str) -> ParseColorError {
+        ParseColorError::InvalidUnit {
+            component,
+            value: self.text.to_string(),
+        }
+    }
+
+    // 色相，单位为度
+    fn hue(&self) -> Result<f32, ParseColorError> {
+        let degrees = match self.unit {
+            Unit::None | Unit::Deg => self.value,
+            Unit::Rad => self.value.to_degrees(),
+            Unit::Grad => self.value * 0.9,
+            Unit::Turn => self.value * 360.0,
+            Unit::Percent => return Err(self.invalid_unit("hue")),
+        };
+        Ok(degrees.rem_euclid(360.0))
+    }
+
+    // 数字或百分比，百分比的 100% 对应 percent_scale
+    fn number(&self, component: &'static str, percent_scale: f32) -> Result<f32, ParseColorError> {
+        match self.unit {
+            Unit::None => Ok(self.value),
+            Unit::Percent => Ok(self.value / 100.0 * percent_scale),
+            _ => Err(self.invalid_unit(component)),
+        }
+    }
+
+    fn number_in(
+        &self,
+        component: &'static str,
+        percent_scale: f32,
+        min: f32,
+        max: f32,
+    ) -> Result<f32, ParseColorError> {
+        let value = self.number(component, percent_scale)?;
+        if (min..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(ParseColorError::OutOfRange { component, value })
+        }
+    }
+
+    // 不透明度，0~1 或百分比
+    fn alpha(&self) -> Result<f32, ParseColorError> {
+        self.number_in("alpha", 1.0, 0.0, 1.0)
+    }
+}
+
+// 开头数字部分的长度，指数写法 1e2、1.5E-3 也算在内
+fn number_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let digits_from = |mut i: usize| {
+        while bytes
+            .get(i)
+            .is_some_and(|b| b.is_ascii_digit() || *b == b'.')
+        {
+            i += 1;
+        }
+        i
+    };
+    let sign = |i: usize| i + matches!(bytes.get(i), Some(b'+' | b'-')) as usize;
+
+    let end = digits_from(sign(0));
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let exponent = sign(end + 1);
+        if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+            return digits_from(exponent);
+        }
+    }
+    end
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    // 支持 #hex、CSS 函数（rgb/rgba/hsl/hsla/hwb/lab/lch/oklab/oklch 以及 hsv）、
+    // CSS 颜色名，以及 Colorose 显示的 "r:255 g:0 b:0"、"h:0.0 s:100.0 v:100.0" 等格式
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseColorError::Empty);
+        }
+        if s.starts_with('#') {
+            return Ok(Color::from_hex(s)?);
+        }
+        if let Some(open) = s.find('(') {
+            let name = s[..open].trim().to_ascii_lowercase();
+            let args = s[open + 1..]
+                .strip_suffix(')')
+                .ok_or(ParseColorError::UnclosedParenthesis)?;
+            return parse_function(&name, args);
+        }
+        if s.contains(':') {
+            return parse_labeled(s);
+        }
+        parse_name(s)
+    }
+}
+
+// 把参数拆成分量和可选的不透明度，同时支持逗号和空格两种写法
+fn split_args<'a>(
+    format: &str,
+    args: &'a str,
+    expected: usize,
+) -> Result<(Vec<Component<'a>>, Option<Component<'a>>), ParseColorError> {
+    let (components, alpha): (Vec<&str>, Option<&str>) = if args.contains(',') {
+        let mut parts: Vec<&str> = args.split(',').map(str::trim).collect();
+        let alpha = (parts.len() == expected + 1).then(|| parts.pop()).flatten();
+        (parts, alpha)
+    } else {
+        let (components, alpha) = match args.split_once('/') {
+            Some((components, alpha)) => (components, Some(alpha.trim())),
+            None => (args, None),
+        };
+        (components.split_whitespace().collect(), alpha)
+    };
+
+    if components.iter().chain(&alpha).any(|c| c.is_empty()) {
+        return Err(ParseColorError::EmptyComponent(format.to_string()));
+    }
+    if components.len() != expected {
+        return Err(ParseColorError::ArgumentCount {
+            format: format.to_string(),
+            expected,
+            found: components.len(),
+        });
+    }
+    let components = components
+        .into_iter()
+        .map(Component::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    let alpha = alpha.map(Component::parse).transpose()?;
+    Ok((components, alpha))
+}
+
+fn with_alpha(color: Color, alpha: Option<Component>) -> Result<Color, ParseColorError> {
+    match alpha {
+        Some(alpha) => Ok(Color {
+            a: (alpha.alpha()? * 255.0).round() as u8,
+            ..color
+        }),
+        None => Ok(color),
+    }
+}
+
+const FUNCTIONS: [&str; 12] = [
+    "rgb", "rgba", "hsl", "hsla", "hsv", "hsva", "hsb", "hwb", "lab", "lch", "oklab", "oklch",
+];
+
+fn parse_function(name: &str, args: &str) -> Result<Color, ParseColorError> {
+    // 先认函数名，未知函数不报分量个数
+    if !FUNCTIONS.contains(&name) {
+        return Err(ParseColorError::UnknownFunction(name.to_string()));
+    }
+    let format = format!("{}()", name);
+    let (c, alpha) = split_args(&format, args, 3)?;
+    let color = match name {
+        "rgb" | "rgba" => {
+            let channel = |c: &Component, component| {
+                c.number_in(component, 255.0, 0.0, 255.0)
+                    .map(|v| v.round() as u8)
+            };
+            Color::new(
+                channel(&c[0], "red")?,
+                channel(&c[1], "green")?,
+                channel(&c[2], "blue")?,
+            )
+        }
+        "hsl" | "hsla" => HSL::new(
+            c[0].hue()?,
+            c[1].number_in("saturation", 100.0, 0.0, 100.0)? / 100.0,
+            c[2].number_in("lightness", 100.0, 0.0, 100.0)? / 100.0,
+        )
+        .to_rgb(),
+        "hsv" | "hsva" | "hsb" => HSV::new(
+            c[0].hue()?,
+            c[1].number_in("saturation", 100.0, 0.0, 100.0)? / 100.0,
+            c[2].number_in("value", 100.0, 0.0, 100.0)? / 100.0,
+        )
+        .to_rgb(),
+        "hwb" => {
+            let hue = c[0].hue()?;
+            let white = c[1].number_in("whiteness", 100.0, 0.0, 100.0)? / 100.0;
+            let black = c[2].number_in("blackness", 100.0, 0.0, 100.0)? / 100.0;
+            hwb_to_rgb(hue, white, black)
+        }
+        // CSS 的 lab() 和 lch() 使用 D50 白点
+        "lab" => Lab::new(
+            c[0].number_in("lightness", 100.0, 0.0, 100.0)?,
+            c[1].number("a", 125.0)?,
+            c[2].number("b", 125.0)?,
+            WhitePoint::D50,
+        )
+        .to_rgb(),
+        "lch" => LCh::new(
+            c[0].number_in("lightness", 100.0, 0.0, 100.0)?,
+            c[1].number_in("chroma", 150.0, 0.0, f32::INFINITY)?,
+            c[2].hue()?,
+            WhitePoint::D50,
+        )
+        .to_rgb(),
+        "oklab" => OKLab::new(
+            c[0].number_in("lightness", 1.0, 0.0, 1.0)?,
+            c[1].number("a", 0.4)?,
+            c[2].number("b", 0.4)?,
+        )
+        .to_rgb(),
+        "oklch" => OKLCh::new(
+            c[0].number_in("lightness", 1.0, 0.0, 1.0)?,
+            c[1].number_in("chroma", 0.4, 0.0, f32::INFINITY)?,
+            c[2].hue()?,
+        )
+        .to_rgb(),
+        _ => return Err(ParseColorError::UnknownFunction(name.to_string())),
+    };
+    with_alpha(color, alpha)
+}
+
+fn hwb_to_rgb(hue: f32, white: f32, black: f32) -> Color {
+    if white + black >= 1.0 {
+        let gray = (white / (white + black) * 255.0).round() as u8;
+        return Color::new(gray, gray, gray);
+    }
+    let v = 1.0 - black;
+    HSV::new(hue, 1.0 - white / v, v).to_rgb()
+}
+
+// Colorose 自己显示的格式，如 "r:255 g:0 b:0 a:128"、"L:53.2 C:104.6 h:40.0 a:0.50"，
+// Lab 和 LCh 末尾的 D50 表示白点，没有时为 D65
+fn parse_labeled(s: &str) -> Result<Color, ParseColorError> {
+    let mut labels = String::new();
+    let mut values = vec![];
+    let mut white = None;
+    for pair in s.split_whitespace() {
+        if pair.eq_ignore_ascii_case("d50") {
+            white = Some(WhitePoint::D50);
+            continue;
+        }
+        if pair.eq_ignore_ascii_case("d65") {
+            white = Some(WhitePoint::D65);
+            continue;
+        }
+        let (label, value) = pair
+            .split_once(':')
+            .ok_or_else(|| ParseColorError::InvalidNumber(pair.to_string()))?;
+        if label.len() != 1 || !label.is_ascii() {
+            return Err(ParseColorError::UnknownLabels(s.to_string()));
+        }
+        labels.push_str(&label.to_ascii_lowercase());
+        values.push(Component::parse(value)?.number("component", 100.0)?);
+    }
+
+    if values.len() < 3 {
+        return Err(ParseColorError::ArgumentCount {
+            format: labels,
+            expected: 3,
+            found: values.len(),
+        });
+    }
+    // 前三个标签决定颜色空间，第四个可选的 a 为不透明度
+    let (space, alpha_label) = labels.split_at(3);
+    if values.len() > 4 || !(alpha_label.is_empty() || alpha_label == "a") {
+        return Err(ParseColorError::UnknownLabels(s.to_string()));
+    }
+    if white.is_some() && space != "lab" && space != "lch" {
+        return Err(ParseColorError::UnknownLabels(s.to_string()));
+    }
+    let white = white.unwrap_or_default();
+    let alpha = values.get(3).copied();
+    let v = |i: usize| values[i];
+    let check = |component: &'static str, value: f32, max: f32| {
+        if (0.0..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(ParseColorError::OutOfRange { component, value })
+        }
+    };
+    // r:g:b 的 a 是 0~255，其余为 0~1
+    let alpha = |max: f32| -> Result<u8, ParseColorError> {
+        match alpha {
+            Some(a) => Ok((check("alpha", a, max)? / max * 255.0).round() as u8),
+            None => Ok(255),
+        }
+    };
+
+    let color = match space {
+        "rgb" => {
+            return Ok(Color::new_rgba(
+                check("red", v(0), 255.0)?.round() as u8,
+                check("green", v(1), 255.0)?.round() as u8,
+                check("blue", v(2), 255.0)?.round() as u8,
+                alpha(255.0)?,
+            ));
+        }
+        "hsl" => HSL::new(
+            v(0).rem_euclid(360.0),
+            check("saturation", v(1), 100.0)? / 100.0,
+            check("lightness", v(2), 100.0)? / 100.0,
+        )
+        .to_rgb(),
+        "hsv" => HSV::new(
+            v(0).rem_euclid(360.0),
+            check("saturation", v(1), 100.0)? / 100.0,
+            check("value", v(2), 100.0)? / 100.0,
+        )
+        .to_rgb(),
+        "lab" => Lab::new(check("lightness", v(0), 100.0)?, v(1), v(2), white).to_rgb(),
+        "lch" => LCh::new(
+            check("lightness", v(0), 100.0)?,
+            check("chroma", v(1), f32::INFINITY)?,
+            v(2).rem_euclid(360.0),
+            white,
+        )
+        .to_rgb(),
+        "xyz" => XYZ::new(v(0) / 100.0, v(1) / 100.0, v(2) / 100.0, WhitePoint::D65).to_rgb(),
+        _ => return Err(ParseColorError::UnknownLabels(s.to_string())),
+    };
+    Ok(Color {
+        a: alpha(1.0)?,
+        ..color
+    })
+}
+
+fn parse_name(s: &str) -> Result<Color, ParseColorError> {
+    let name = s.to_ascii_lowercase();
+    if name == "transparent" {
+        return Ok(Color::new_rgba(0, 0, 0, 0));
+    }
+    CSS.iter()
+        .find(|(css_name, _)| *css_name == name)
+        .map(|(_, rgb)| Color::from(*rgb))
+        .ok_or_else(|| ParseColorError::UnknownName(s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        color::{Color, parse::ParseColorError},
+        readout::Readout,
+    };
+
+    fn parse(s: &str) -> Result<Color, ParseColorError> {
+        s.parse::<Color>()
+    }
+
+    fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color::new_rgba(r, g, b, a)
+    }
+
+    fn assert_parses(cases: &[(&str, Color)]) {
+        for (text, expected) in cases {
+            assert_eq!(parse(text), Ok(*expected), "{}", text);
+        }
+    }
+
+    fn assert_close(text: &str, expected: Color) {
+        assert_within(text, expected, 1);
+    }
+
+    fn assert_within(text: &str, expected: Color, tolerance: u8) {
+        let color = parse(text).unwrap_or_else(|e| panic!("{}: {}", text, e));
+        let channels = |c: Color| [c.r, c.g, c.b, c.a];
+        for (a, e) in channels(color).into_iter().zip(channels(expected)) {
+            assert!(
+                a.abs_diff(e) <= tolerance,
+                "{}: {:?} != {:?}",
+                text,
+                color,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn hex() {
+        assert_parses(&[
+            ("#f00", Color::new(255, 0, 0)),
+            ("#F00A", rgba(255, 0, 0, 170)),
+            ("  #87ceeb  ", Color::new(135, 206, 235)),
+            ("#ff000080", rgba(255, 0, 0, 128)),
+        ]);
+    }
+
+    #[test]
+    fn rgb() {
+        let red = Color::new(255, 0, 0);
+        assert_parses(&[
+            ("rgb(255, 0, 0)", red),
+            ("rgb(255 0 0)", red),
+            ("RGB( 255 0 0 )", red),
+            ("rgb(100% 0% 0%)", red),
+            ("rgb(2.55e2 0 none)", red),
+            ("rgb(1e2 1E1 +1e+0)", Color::new(100, 10, 1)),
+            ("rgb(12.5e-1 0 0)", Color::new(1, 0, 0)),
+            ("rgba(255, 0, 0, 0.5)", rgba(255, 0, 0, 128)),
+            ("rgb(255, 0, 0, 50%)", rgba(255, 0, 0, 128)),
+            ("rgba(255 0 0 / 0)", rgba(255, 0, 0, 0)),
+            ("rgb(255 0 0 / 25%)", rgba(255, 0, 0, 64)),
+        ]);
+    }
+
+    #[test]
+    fn hsl_hsv_and_hwb() {
+        assert_parses(&[
+            ("hsl(120 100% 50%)", Color::new(0, 255, 0)),
+            ("hsl(120deg, 100%, 25%)", Color::new(0, 128, 0)),
+            ("hsla(0.5turn 100% 50% / 0.25)", rgba(0, 255, 255, 64)),
+            ("hsl(400grad 100% 50%)", Color::new(255, 0, 0)),
+            ("hsl(-120 100 50)", Color::new(0, 0, 255)),
+            ("hsv(0 100% 100%)", Color::new(255, 0, 0)),
+            ("hsb(240, 100%, 50%)", Color::new(0, 0, 128)),
+            ("hsva(60 100 100 / 1)", Color::new(255, 255, 0)),
+            ("hwb(0 0% 0%)", Color::new(255, 0, 0)),
+            ("hwb(120 20% 20%)", Color::new(51, 204, 51)),
+            ("hwb(0 60% 60%)", Color::new(128, 128, 128)),
+        ]);
+        assert_close("hsl(3.14159rad 100% 50%)", Color::new(0, 255, 255));
+    }
+
+    // CSS 的 lab() 和 lch() 为 D50
+    #[test]
+    fn lab_and_lch() {
+        let red = Color::new(255, 0, 0);
+        assert_close("lab(54.29 80.81 69.89)", red);
+        assert_close("lab(54.29% 64.65% 55.91%)", red);
+        assert_close("lch(54.29 106.84 40.85)", red);
+        assert_close("lch(54.29 71.23% 40.85deg / 50%)", rgba(255, 0, 0, 128));
+        assert_eq!(parse("lab(100 0 0)"), Ok(Color::new(255, 255, 255)));
+        assert_eq!(parse("lch(0 0 0)"), Ok(Color::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn oklab_and_oklch() {
+        let red = Color::new(255, 0, 0);
+        assert_close("oklab(62.8% 0.225 0.126)", red);
+        assert_close("oklab(0.628 56.25% 31.5%)", red);
+        assert_close("oklch(62.8% 0.258 29.23)", red);
+        assert_close("oklch(0.628 64.5% 29.23 / 0.5)", rgba(255, 0, 0, 128));
+        assert_eq!(parse("oklch(100% 0 0)"), Ok(Color::new(255, 255, 255)));
+    }
+
+    #[test]
+    fn names() {
+        assert_parses(&[
+            ("red", Color::new(255, 0, 0)),
+            ("RebeccaPurple", Color::new(102, 51, 153)),
+            (" grey ", Color::new(128, 128, 128)),
+            ("transparent", rgba(0, 0, 0, 0)),
+        ]);
+        // 不再把不认识的名字当作不带 # 的十六进制
+        for name in ["bad", "cafe", "ff0000", "notacolor"] {
+            assert_eq!(
+                parse(name),
+                Err(ParseColorError::UnknownName(name.to_string()))
+            );
+        }
+    }
+
+    // 每个读数行的文本都能解析回原来的颜色，包括 D50 的 Lab 和 LCh，
+    // XYZ 只显示一位小数，暗的分量误差大一些
+    #[test]
+    fn readouts_round_trip() {
+        let readouts = Readout::FIXED
+            .into_iter()
+            .chain(Readout::OPTIONAL)
+            .filter(|r| !matches!(r, Readout::Xterm256 | Readout::ANSI16));
+        for readout in readouts {
+            let tolerance = if readout == Readout::XYZ { 4 } else { 1 };
+            for color in [
+                Color::new(255, 0, 0),
+                Color::new(18, 52, 86),
+                Color::new(128, 128, 128),
+                rgba(135, 206, 235, 128),
+                rgba(0, 0, 0, 0),
+            ] {
+                assert_within(&readout.format(&color), color, tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn labeled_white_point() {
+        let d65 = parse("L:54.3 a:80.8 b:69.9").unwrap();
+        let d50 = parse("L:54.3 a:80.8 b:69.9 D50").unwrap();
+        assert_ne!(d65, d50);
+        assert_close("L:54.3 a:80.8 b:69.9 D50", Color::new(255, 0, 0));
+        assert_close("L:53.2 a:80.1 b:67.2 d65", Color::new(255, 0, 0));
+        assert_close("L:54.3 C:106.8 h:40.9 a:0.50 D50", rgba(255, 0, 0, 128));
+        assert_eq!(
+            parse("r:255 g:0 b:0 D50"),
+            Err(ParseColorError::UnknownLabels(
+                "r:255 g:0 b:0 D50".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        use ParseColorError::*;
+        let count = |format: &str, found| ArgumentCount {
+            format: format.to_string(),
+            expected: 3,
+            found,
+        };
+        let invalid_number = |value: &str| InvalidNumber(value.to_string());
+        let invalid_unit = |component, value: &str| InvalidUnit {
+            component,
+            value: value.to_string(),
+        };
+        for (text, error) in [
+            ("", Empty),
+            ("   ", Empty),
+            ("#ggg", InvalidHex("#ggg".to_string())),
+            ("#12345", InvalidHex("#12345".to_string())),
+            ("nope", UnknownName("nope".to_string())),
+            ("color(srgb 1 0 0)", UnknownFunction("color".to_string())),
+            ("rgb(1 2 3", UnclosedParenthesis),
+            ("rgb(,1,2)", EmptyComponent("rgb()".to_string())),
+            ("rgb(1,,2)", EmptyComponent("rgb()".to_string())),
+            ("rgb(1,2,3,)", EmptyComponent("rgb()".to_string())),
+            ("hsl(1 2 3 /)", EmptyComponent("hsl()".to_string())),
+            ("rgb()", count("rgb()", 0)),
+            ("rgb(1 2)", count("rgb()", 2)),
+            ("rgb(1 2 3 4)", count("rgb()", 4)),
+            ("rgb(1,2,3,4,5)", count("rgb()", 5)),
+            ("r:1 g:2", count("rg", 2)),
+            ("rgb(x 0 0)", invalid_number("x")),
+            ("rgb(1e 0 0)", invalid_number("1e")),
+            ("rgb(1em 0 0)", invalid_number("1em")),
+            ("rgb(1.2.3 0 0)", invalid_number("1.2.3")),
+            ("rgb(inf 0 0)", invalid_number("inf")),
+            ("r:x g:0 b:0", invalid_number("x")),
+            ("r:1 g:2 b3", invalid_number("b3")),
+            ("rgb(10deg 0 0)", invalid_unit("red", "10deg")),
+            ("hsl(10% 50% 50%)", invalid_unit("hue", "10%")),
+            ("rgb(0 0 0 / 1turn)", invalid_unit("alpha", "1turn")),
+            (
+                "rgb(256 0 0)",
+                OutOfRange {
+                    component: "red",
+                    value: 256.0,
+                },
+            ),
+            (
+                "rgba(0, 0, 0, 2)",
+                OutOfRange {
+                    component: "alpha",
+                    value: 2.0,
+                },
+            ),
+            (
+                "hsl(0 101% 50%)",
+                OutOfRange {
+                    component: "saturation",
+                    value: 101.0,
+                },
+            ),
+            (
+                "oklch(0.5 -0.1 0)",
+                OutOfRange {
+                    component: "chroma",
+                    value: -0.1,
+                },
+            ),
+            (
+                "h:0 s:50 l:150",
+                OutOfRange {
+                    component: "lightness",
+                    value: 150.0,
+                },
+            ),
+            (
+                "r:0 g:0 b:0 a:256",
+                OutOfRange {
+                    component: "alpha",
+                    value: 256.0,
+                },
+            ),
+            ("x:1 y:2 q:3", UnknownLabels("x:1 y:2 q:3".to_string())),
+            ("rg:1 g:2 b:3", UnknownLabels("rg:1 g:2 b:3".to_string())),
+            (
+                "r:1 g:2 b:3 x:4",
+                UnknownLabels("r:1 g:2 b:3 x:4".to_string()),
+            ),
+            (
+                "r:1 g:2 b:3 a:4 a:5",
+                UnknownLabels("r:1 g:2 b:3 a:4 a:5".to_string()),
+            ),
+        ] {
+            assert_eq!(parse(text), Err(error), "{}", text);
+        }
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            parse("rgb(,1,2)").unwrap_err().to_string(),
+            "rgb() has an empty component"
+        );
+        assert_eq!(
+            parse("rgb(1 2)").unwrap_err().to_string(),
+            "rgb() expects 3 components (plus optional alpha), got 2"
+        );
+        assert_eq!(
+            parse("hsl(10% 50% 50%)").unwrap_err().to_string(),
+            "invalid unit for hue: 10%"
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    color::Color, position::Position, sample_aggregator::SampleAggregator,
+    sample_shape::SampleShape, sample_space::SampleSpace,
+};
+
+// 一次确认的取色，记录当时的位置和采样设置
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub color: Color,
+    pub timestamp: u64, // Unix 时间，秒
+    pub position: Position,
+    pub sample_size: usize,
+    pub sample_shape: SampleShape,
+    pub sample_aggregator: SampleAggregator,
+    pub sample_space: SampleSpace,
+}
+
+// 取色历史，按时间从旧到新，超出数量或过期的记录在加入和加载时删除
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    entries: Vec<HistoryEntry>,
+    #[serde(skip)]
+    dirty: bool, // 有未保存的修改
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryError::Io(e) => write!(f, "unable to access history: {}", e),
+            HistoryError::Json(e) => write!(f, "invalid history file: {}", e),
+        }
+    }
+}
+
+impl Error for HistoryError {}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl HistoryEntry {
+    // 距今多久，如 "5m ago"
+    pub fn age(&self, now: u64) -> String {
+        let seconds = now.saturating_sub(self.timestamp);
+        match seconds {
+            0..60 => "just now".to_string(),
+            60..3600 => format!("{}m ago", seconds / 60),
+            3600..86400 => format!("{}h ago", seconds / 3600),
+            _ => format!("{}d ago", seconds / 86400),
+        }
+    }
+}
+
+impl Display for HistoryEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} {}", self.color.to_hex_auto(), self.age(now()))?;
+        writeln!(f, "{}", self.position)?;
+        write!(
+            f,
+            "sample {} {} {} ({})",
+            self.sample_size,
+            self.sample_shape.name(),
+            self.sample_aggregator.name(),
+            self.sample_space.name()
+        )
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new()
+    }
+}
+
+impl History {
+    pub const MAX_ENTRIES: usize = 500;
+    pub const MAX_AGE: u64 = 90 * 24 * 60 * 60; // 90 天
+
+    pub fn new() -> Self {
+        History {
+            entries: vec![],
+            dirty: false,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, HistoryError> {
+        let mut history: History = serde_json::from_str(json).map_err(HistoryError::Json)?;
+        history.prune(now());
+        Ok(history)
+    }
+
+    pub fn to_json(&self) -> Result<String, HistoryError> {
+        serde_json::to_string_pretty(self).map_err(HistoryError::Json)
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    // 和上一条颜色、位置都相同时只更新时间
+    pub fn push(&mut self, entry: HistoryEntry) {
+        match self.entries.last_mut() {
+            Some(last) if last.color == entry.color && last.position == entry.position => {
+                last.timestamp = entry.timestamp;
+            }
+            _ => self.entries.push(entry),
+        }
+        self.prune(entry.timestamp);
+        self.dirty = true;
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+            self.dirty = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dirty = true;
+    }
+
+    // 删除过期和超出数量的旧记录
+    pub fn prune(&mut self, now: u64) {
+        let len = self.entries.len();
+        self.entries
+            .retain(|entry| now.saturating_sub(entry.timestamp) <= Self::MAX_AGE);
+        let excess = self.entries.len().saturating_sub(Self::MAX_ENTRIES);
+        self.entries.drain(..excess);
+        self.dirty |= self.entries.len() != len;
+    }
+
+    // 返回是否有未保存的修改，并清除标记
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        color::Color,
+        history::{History, HistoryEntry, HistoryError, now},
+        position::Position,
+        sample_aggregator::SampleAggregator,
+        sample_shape::SampleShape,
+        sample_space::SampleSpace,
+    };
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn entry(value: u8, timestamp: u64) -> HistoryEntry {
+        HistoryEntry {
+            color: Color::new(value, 0, 0),
+            timestamp,
+            position: Position { x: 1, y: 2 },
+            sample_size: 1,
+            sample_shape: SampleShape::Square,
+            sample_aggregator: SampleAggregator::Mean,
+            sample_space: SampleSpace::SRGB,
+        }
+    }
+
+    fn values(history: &History) -> Vec<u8> {
+        history.entries().iter().map(|e| e.color.r).collect()
+    }
+
+    #[test]
+    fn repeated_pick_only_updates_the_time() {
+        let mut history = History::new();
+        history.push(entry(1, 100));
+        history.push(entry(1, 200));
+        assert_eq!(history.entries().len(), 1);
+        assert_eq!(history.entries()[0].timestamp, 200);
+
+        // 位置不同或不是最后一条时照常记录
+        let mut moved = entry(1, 300);
+        moved.position = Position { x: 5, y: 2 };
+        history.push(moved);
+        history.push(entry(2, 400));
+        history.push(entry(1, 500));
+        assert_eq!(values(&history), [1, 1, 2, 1]);
+    }
+
+    #[test]
+    fn keeps_at_most_max_entries() {
+        let mut history = History::new();
+        for i in 0..History::MAX_ENTRIES + 3 {
+            history.push(entry((i % 2) as u8, 1000 + i as u64));
+        }
+        assert_eq!(history.entries().len(), History::MAX_ENTRIES);
+        // 删除的是最旧的
+        assert_eq!(history.entries()[0].timestamp, 1003);
+        assert_eq!(
+            history.entries().last().unwrap().timestamp,
+            1000 + History::MAX_ENTRIES as u64 + 2
+        );
+    }
+
+    #[test]
+    fn prunes_entries_older_than_max_age() {
+        let start = 1000 * DAY;
+        let mut history = History::new();
+        history.push(entry(1, start));
+        history.push(entry(2, start + 10 * DAY));
+        history.push(entry(3, start + History::MAX_AGE));
+        assert_eq!(values(&history), [1, 2, 3]);
+
+        // 刚好 90 天的还保留
+        history.push(entry(4, start + History::MAX_AGE + 1));
+        assert_eq!(values(&history), [2, 3, 4]);
+
+        history.take_dirty();
+        history.prune(start + 10 * DAY + History::MAX_AGE);
+        assert_eq!(values(&history), [2, 3, 4]);
+        assert!(!history.take_dirty());
+        history.prune(start + 200 * DAY);
+        assert!(history.entries().is_empty());
+        assert!(history.take_dirty());
+    }
+
+    #[test]
+    fn remove_clear_and_dirty_flag() {
+        let mut history = History::new();
+        assert!(!history.take_dirty());
+        history.push(entry(1, 100));
+        history.push(entry(2, 200));
+        assert!(history.take_dirty());
+        assert!(!history.take_dirty());
+
+        history.remove(5);
+        assert!(!history.take_dirty());
+        history.remove(0);
+        assert_eq!(values(&history), [2]);
+        assert!(history.take_dirty());
+        history.clear();
+        assert!(history.entries().is_empty());
+        assert!(history.take_dirty());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let now = now();
+        let mut history = History::new();
+        let mut first = entry(10, now - 60);
+        first.sample_size = 5;
+        first.sample_shape = SampleShape::Circle;
+        first.sample_aggregator = SampleAggregator::VectorMedian;
+        first.sample_space = SampleSpace::OKLab;
+        history.push(first);
+        history.push(entry(20, now));
+
+        let json = history.to_json().unwrap();
+        assert!(json.contains("\"vector_median\""), "{}", json);
+        assert!(!json.contains("dirty"));
+        let loaded = History::from_json(&json).unwrap();
+        assert_eq!(loaded.entries(), history.entries());
+    }
+
+    #[test]
+    fn loading_prunes_and_rejects_bad_json() {
+        let now = now();
+        // push 按新记录的时间删除过期记录，先加新的才能把过期的写进文件
+        let mut history = History::new();
+        history.push(entry(2, now));
+        history.push(entry(1, now - History::MAX_AGE - DAY));
+        assert_eq!(values(&history), [2, 1]);
+        let mut loaded = History::from_json(&history.to_json().unwrap()).unwrap();
+        assert_eq!(values(&loaded), [2]);
+        assert!(loaded.take_dirty());
+
+        assert!(matches!(
+            History::from_json("{"),
+            Err(HistoryError::Json(_))
+        ));
+        assert!(matches!(
+            History::from_json(r#"{"entries": [{"color": 1}]}"#),
+            Err(HistoryError::Json(_))
+        ));
+        assert!(
+            History::from_json(r#"{"entries": []}"#)
+                .unwrap()
+                .entries()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn age() {
+        let entry = entry(0, 1_000_000);
+        assert_eq!(entry.age(1_000_000), "just now");
+        assert_eq!(entry.age(999_000), "just now");
+        assert_eq!(entry.age(1_000_059), "just now");
+        assert_eq!(entry.age(1_000_060), "1m ago");
+        assert_eq!(entry.age(1_000_000 + 3599), "59m ago");
+        assert_eq!(entry.age(1_000_000 + 3600), "1h ago");
+        assert_eq!(entry.age(1_000_000 + DAY), "1d ago");
+        assert_eq!(entry.age(1_000_000 + 45 * DAY), "45d ago");
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt::Display;
+
+use egui::{Event, InputState, Key, Modifiers};
+
+// 快捷键，由平台层轮询按键状态实现全局触发，窗口有焦点时也可以由 egui 处理
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hotkey {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub key: char, // 字母或数字，大写
+}
+
+impl Hotkey {
+    // 复制主格式
+    pub const COPY: Hotkey = Hotkey {
+        ctrl: true,
+        shift: true,
+        alt: false,
+        key: 'C',
+    };
+
+    // 确认取色，记入历史
+    pub const PICK: Hotkey = Hotkey {
+        ctrl: true,
+        shift: true,
+        alt: false,
+        key: 'P',
+    };
+
+    // 冻结/解冻取色
+    pub const FREEZE: Hotkey = Hotkey {
+        ctrl: true,
+        shift: true,
+        alt: false,
+        key: 'X',
+    };
+
+    // 窗口内的按键事件
+    pub fn pressed(&self, input: &InputState) -> bool {
+        let Some(key) = Key::from_name(&self.key.to_string()) else {
+            return false;
+        };
+        let modifiers = Modifiers {
+            alt: self.alt,
+            ctrl: self.ctrl,
+            shift: self.shift,
+            mac_cmd: false,
+            command: self.ctrl,
+        };
+        if !input.modifiers.matches_exact(modifiers) {
+            return false;
+        }
+        // egui-winit 把 Ctrl+C 转成 Copy 事件，不再产生按键事件
+        let copy = key == Key::C && self.ctrl && input.events.contains(&Event::Copy);
+        copy || input.key_pressed(key)
+    }
+}
+
+impl Display for Hotkey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+// 轮询按键状态时，只在按下的瞬间触发一次
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HotkeyEdge {
+    down: bool,
+}
+
+impl HotkeyEdge {
+    pub fn pressed(&mut self, down: bool) -> bool {
+        let pressed = down && !self.down;
+        self.down = down;
+        pressed
+    }
+}
@@ -1,3 +1,12 @@
 pub mod color;
+pub mod history;
+pub mod hotkey;
+pub mod named_color;
+pub mod palette;
 pub mod position;
+pub mod readout;
+pub mod sample_aggregator;
+pub mod sample_shape;
+pub mod sample_space;
+pub mod template;
 pub mod wheel_mode;
\ No newline at end of file
@@ -0,0 +1,313 @@
+use std::{collections::BTreeMap, error::Error, fmt::Display};
+
+use serde::Deserialize;
+
+use crate::model::color::{
+    Color, ColorConversionError,
+    cie::{Lab, WhitePoint},
+    difference::delta_e2000,
+};
+
+pub mod chinese;
+pub mod css;
+pub mod x11;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedColor {
+    pub name: String,
+    pub color: Color,
+    lab: Lab, // 预先算好，查找时不用每次转换
+}
+
+// 一组命名颜色，按 ΔE2000 查找最接近的名字
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorDictionary {
+    pub name: String,
+    entries: Vec<NamedColor>,
+}
+
+#[derive(Debug)]
+pub enum DictionaryError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Csv(csv::Error),
+    InvalidColor {
+        name: String,
+        error: ColorConversionError,
+    },
+    UnsupportedFormat(String),
+    Empty,
+}
+
+impl Display for DictionaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DictionaryError::Io(e) => write!(f, "unable to read dictionary: {}", e),
+            DictionaryError::Json(e) => write!(f, "invalid json dictionary: {}", e),
+            DictionaryError::Csv(e) => write!(f, "invalid csv dictionary: {}", e),
+            DictionaryError::InvalidColor { name, error } => {
+                write!(f, "invalid color for \"{}\": {}", name, error)
+            }
+            DictionaryError::UnsupportedFormat(ext) => {
+                write!(f, "unsupported dictionary format: {}", ext)
+            }
+            DictionaryError::Empty => write!(f, "dictionary has no colors"),
+        }
+    }
+}
+
+impl Error for DictionaryError {}
+
+// JSON 词典可以是 {"名字": "#hex"}，也可以是 [{"name": "名字", "hex": "#hex"}]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonDictionary {
+    Map(BTreeMap<String, String>),
+    List(Vec<JsonEntry>),
+}
+
+#[derive(Deserialize)]
+struct JsonEntry {
+    name: String,
+    #[serde(alias = "color")]
+    hex: String,
+}
+
+impl NamedColor {
+    pub fn new(name: impl Into<String>, color: Color) -> Self {
+        NamedColor {
+            name: name.into(),
+            color,
+            lab: color.to_lab(WhitePoint::D65),
+        }
+    }
+}
+
+impl ColorDictionary {
+    pub fn new(name: impl Into<String>, entries: Vec<NamedColor>) -> Self {
+        ColorDictionary {
+            name: name.into(),
+            entries,
+        }
+    }
+
+    fn from_table(name: &str, table: &[(&str, [u8; 3])]) -> Self {
+        let entries = table
+            .iter()
+            .map(|(name, rgb)| NamedColor::new(*name, Color::from(*rgb)))
+            .collect();
+        ColorDictionary::new(name, entries)
+    }
+
+    pub fn css() -> Self {
+        Self::from_table("CSS", css::CSS)
+    }
+
+    pub fn x11() -> Self {
+        Self::from_table("X11", x11::X11)
+    }
+
+    pub fn chinese() -> Self {
+        Self::from_table("中国传统色", chinese::CHINESE)
+    }
+
+    pub fn builtin() -> Vec<Self> {
+        vec![Self::css(), Self::x11(), Self::chinese()]
+    }
+
+    pub fn from_json(name: impl Into<String>, json: &str) -> Result<Self, DictionaryError> {
+        let pairs: Vec<(String, String)> =
+            match serde_json::from_str(json).map_err(DictionaryError::Json)? {
+                JsonDictionary::Map(map) => map.into_iter().collect(),
+                JsonDictionary::List(list) => list.into_iter().map(|e| (e.name, e.hex)).collect(),
+            };
+        Self::from_pairs(name, pairs)
+    }
+
+    // 每行 name,hex，第一行的颜色不以 # 开头且无法解析时当作表头跳过
+    pub fn from_csv(name: impl Into<String>, csv: &str) -> Result<Self, DictionaryError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(csv.as_bytes());
+        let mut pairs = vec![];
+        for (i, record) in reader.records().enumerate() {
+            let record = record.map_err(DictionaryError::Csv)?;
+            let (Some(name), Some(hex)) = (record.get(0), record.get(1)) else {
+                continue;
+            };
+            if i == 0 && !hex.starts_with('#') && Color::from_hex(hex).is_err() {
+                continue;
+            }
+            pairs.push((name.to_string(), hex.to_string()));
+        }
+        Self::from_pairs(name, pairs)
+    }
+
+    fn from_pairs(
+        name: impl Into<String>,
+        pairs: Vec<(String, String)>,
+    ) -> Result<Self, DictionaryError> {
+        let entries = pairs
+            .into_iter()
+            .map(|(name, hex)| match Color::from_hex(&hex) {
+                Ok(color) => Ok(NamedColor::new(name, color)),
+                Err(error) => Err(DictionaryError::InvalidColor { name, error }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if entries.is_empty() {
+            return Err(DictionaryError::Empty);
+        }
+        Ok(ColorDictionary::new(name, entries))
+    }
+
+    pub fn entries(&self) -> &[NamedColor] {
+        &self.entries
+    }
+
+    // 最接近的颜色及其 ΔE2000，忽略不透明度
+    pub fn nearest(&self, color: &Color) -> Option<(&NamedColor, f32)> {
+        let lab = color.to_lab(WhitePoint::D65);
+        self.entries
+            .iter()
+            .map(|entry| (entry, delta_e2000(&lab, &entry.lab)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        color::Color,
+        named_color::{ColorDictionary, DictionaryError, NamedColor},
+    };
+
+    fn names(dictionary: &ColorDictionary) -> Vec<(&str, Color)> {
+        dictionary
+            .entries()
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.color))
+            .collect()
+    }
+
+    #[test]
+    fn nearest_finds_exact_and_close_colors() {
+        let css = ColorDictionary::css();
+        let (entry, distance) = css.nearest(&Color::new(255, 0, 0)).unwrap();
+        assert_eq!(entry.name, "red");
+        assert_eq!(distance, 0.0);
+
+        let (entry, distance) = css.nearest(&Color::new(250, 5, 3)).unwrap();
+        assert_eq!(entry.name, "red");
+        assert!(distance > 0.0 && distance < 3.0);
+
+        // 不透明度不影响结果
+        let (entry, _) = css.nearest(&Color::new_rgba(0, 0, 255, 10)).unwrap();
+        assert_eq!(entry.name, "blue");
+
+        let two = ColorDictionary::new(
+            "two",
+            vec![
+                NamedColor::new("dark", Color::new(20, 20, 20)),
+                NamedColor::new("light", Color::new(230, 230, 230)),
+            ],
+        );
+        assert_eq!(two.nearest(&Color::new(90, 90, 90)).unwrap().0.name, "dark");
+        assert_eq!(
+            two.nearest(&Color::new(160, 160, 160)).unwrap().0.name,
+            "light"
+        );
+        assert!(
+            ColorDictionary::new("empty", vec![])
+                .nearest(&Color::default())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn from_json_map_and_list() {
+        let map = ColorDictionary::from_json("map", r##"{"b": "#0000ff", "a": "#f00"}"##).unwrap();
+        assert_eq!(map.name, "map");
+        assert_eq!(
+            names(&map),
+            [("a", Color::new(255, 0, 0)), ("b", Color::new(0, 0, 255))]
+        );
+
+        let list = ColorDictionary::from_json(
+            "list",
+            r##"[{"name": "z", "hex": "#00ff00"}, {"name": "y", "color": "#000000"}]"##,
+        )
+        .unwrap();
+        assert_eq!(
+            names(&list),
+            [("z", Color::new(0, 255, 0)), ("y", Color::new(0, 0, 0))]
+        );
+    }
+
+    #[test]
+    fn from_json_rejects_bad_input() {
+        assert!(matches!(
+            ColorDictionary::from_json("x", "{"),
+            Err(DictionaryError::Json(_))
+        ));
+        assert!(matches!(
+            ColorDictionary::from_json("x", r#"[{"title": "a"}]"#),
+            Err(DictionaryError::Json(_))
+        ));
+        assert!(matches!(
+            ColorDictionary::from_json("x", r##"{"ok": "#fff", "bad": "#ggg"}"##),
+            Err(DictionaryError::InvalidColor { name, .. }) if name == "bad"
+        ));
+        assert!(matches!(
+            ColorDictionary::from_json("x", "{}"),
+            Err(DictionaryError::Empty)
+        ));
+        assert!(matches!(
+            ColorDictionary::from_json("x", "[]"),
+            Err(DictionaryError::Empty)
+        ));
+    }
+
+    #[test]
+    fn from_csv_skips_header() {
+        let expected = [
+            ("red", Color::new(255, 0, 0)),
+            ("sky", Color::new(135, 206, 235)),
+        ];
+
+        let with_header =
+            ColorDictionary::from_csv("csv", "name,hex\nred,#ff0000\nsky, #87ceeb \n");
+        assert_eq!(names(&with_header.unwrap()), expected);
+
+        let without_header = ColorDictionary::from_csv("csv", "red,#ff0000\nsky,#87ceeb\n");
+        assert_eq!(names(&without_header.unwrap()), expected);
+
+        // 只有一列的行跳过，多余的列忽略
+        let ragged = ColorDictionary::from_csv("csv", "red,#ff0000,extra\nnote\nsky,#87ceeb\n");
+        assert_eq!(names(&ragged.unwrap()), expected);
+    }
+
+    #[test]
+    fn from_csv_rejects_bad_input() {
+        // 只有第一行可以当作表头
+        assert!(matches!(
+            ColorDictionary::from_csv("csv", "red,#ff0000\nname,hex\n"),
+            Err(DictionaryError::InvalidColor { name, .. }) if name == "name"
+        ));
+        assert!(matches!(
+            ColorDictionary::from_csv("csv", "name,hex\nred,#nothex\n"),
+            Err(DictionaryError::InvalidColor { name, .. }) if name == "red"
+        ));
+        assert!(matches!(
+            ColorDictionary::from_csv("csv", "red,#ff000\n"),
+            Err(DictionaryError::InvalidColor { name, .. }) if name == "red"
+        ));
+        for empty in ["", "\n\n", "name,hex\n"] {
+            assert!(matches!(
+                ColorDictionary::from_csv("csv", empty),
+                Err(DictionaryError::Empty)
+            ));
+        }
+    }
+}
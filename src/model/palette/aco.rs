@@ -0,0 +1,104 @@
+use crate::model::{
+    color::{
+        Color, HSV,
+        cie::{Lab, WhitePoint},
+    },
+    palette::{
+        Palette, PaletteError, PaletteFormat, Swatch,
+        binary::{Reader, cmyk_to_rgb, push_utf16, unit_to_u8, utf16_with_nul},
+    },
+};
+
+const RGB: u16 = 0;
+const HSB: u16 = 1;
+const CMYK: u16 = 2;
+const LAB: u16 = 7;
+const GRAY: u16 = 8;
+
+// 版本 1 只有颜色，Photoshop 之后紧接着写一份带名字的版本 2，有版本 2 时以它为准
+pub fn read(name: String, bytes: &[u8]) -> Result<Palette, PaletteError> {
+    let mut reader = Reader::new(bytes);
+    let mut swatches = match reader.u16() {
+        Ok(1) => read_section(&mut reader, false)?,
+        Ok(2) => read_section(&mut reader, true)?,
+        _ => return Err(PaletteError::InvalidHeader(PaletteFormat::ACO)),
+    };
+    if !reader.is_empty() && reader.u16()? == 2 {
+        swatches = read_section(&mut reader, true)?;
+    }
+    Ok(Palette { name, swatches })
+}
+
+fn read_section(reader: &mut Reader, named: bool) -> Result<Vec<Swatch>, PaletteError> {
+    let count = reader.u16()?;
+    let mut swatches = vec![];
+    for _ in 0..count {
+        let space = reader.u16()?;
+        let values = [reader.u16()?, reader.u16()?, reader.u16()?, reader.u16()?];
+        let name = if named {
+            let len = reader.u32()? as usize;
+            reader.utf16(len)?
+        } else {
+            String::new()
+        };
+        swatches.push(Swatch::new(name, decode(space, values)?));
+    }
+    Ok(swatches)
+}
+
+fn decode(space: u16, [w, x, y, z]: [u16; 4]) -> Result<Color, PaletteError> {
+    let unit = |value: u16| value as f32 / u16::MAX as f32;
+    match space {
+        RGB => Ok(Color::new(
+            unit_to_u8(unit(w)),
+            unit_to_u8(unit(x)),
+            unit_to_u8(unit(y)),
+        )),
+        HSB => Ok(HSV::new(unit(w) * 360.0, unit(x), unit(y)).to_rgb()),
+        // 0 表示 100% 的油墨
+        CMYK => Ok(cmyk_to_rgb(
+            1.0 - unit(w),
+            1.0 - unit(x),
+            1.0 - unit(y),
+            1.0 - unit(z),
+        )),
+        // L 为 0~10000，a、b 为有符号的 -12800~12700，白点 D50
+        LAB => Ok(Lab::new(
+            w as f32 / 100.0,
+            x as i16 as f32 / 100.0,
+            y as i16 as f32 / 100.0,
+            WhitePoint::D50,
+        )
+        .to_rgb()),
+        // 0~10000 的油墨量，10000 为黑
+        GRAY => {
+            let gray = unit_to_u8(1.0 - w.min(10000) as f32 / 10000.0);
+            Ok(Color::new(gray, gray, gray))
+        }
+        _ => Err(PaletteError::UnsupportedColorSpace(format!(
+            "ACO space {}",
+            space
+        ))),
+    }
+}
+
+// 写版本 1 和版本 2 两段，颜色都用 RGB
+pub fn write(palette: &Palette) -> Vec<u8> {
+    let mut bytes = vec![];
+    for version in [1u16, 2] {
+        bytes.extend(version.to_be_bytes());
+        bytes.extend((palette.swatches.len() as u16).to_be_bytes());
+        for swatch in &palette.swatches {
+            bytes.extend(RGB.to_be_bytes());
+            for value in [swatch.color.r, swatch.color.g, swatch.color.b, 0] {
+                bytes.extend((value as u16 * 257).to_be_bytes());
+            }
+            if version == 2 {
+                let name = utf16_with_nul(&swatch.name);
+                bytes.extend((name.len() as u32).to_be_bytes());
+                push_utf16(&mut bytes, &name);
+            }
+        }
+    }
+    bytes
+}
@@ -0,0 +1,89 @@
+use crate::model::{
+    color::{
+        Color,
+        cie::{Lab, WhitePoint},
+    },
+    palette::{
+        Palette, PaletteError, PaletteFormat, Swatch,
+        binary::{Reader, cmyk_to_rgb, push_utf16, unit_to_u8, utf16_with_nul},
+    },
+};
+
+const SIGNATURE: &[u8] = b"ASEF";
+const COLOR_ENTRY: u16 = 0x0001;
+const NORMAL_COLOR: u16 = 2; // 0 全局色，1 专色，2 普通颜色
+
+// 文件头 ASEF、版本 1.0、块数，之后每块为类型、长度和内容
+// 分组的开始和结束块跳过，组内的颜色按顺序放进同一个色板
+pub fn read(name: String, bytes: &[u8]) -> Result<Palette, PaletteError> {
+    let mut reader = Reader::new(bytes);
+    if reader.take(4).ok() != Some(SIGNATURE) {
+        return Err(PaletteError::InvalidHeader(PaletteFormat::ASE));
+    }
+    let _version = (reader.u16()?, reader.u16()?);
+    let count = reader.u32()?;
+
+    let mut palette = Palette::new(name);
+    for _ in 0..count {
+        let kind = reader.u16()?;
+        let len = reader.u32()? as usize;
+        let mut block = Reader::new(reader.take(len)?);
+        if kind != COLOR_ENTRY {
+            continue;
+        }
+        let name_len = block.u16()? as usize;
+        let name = block.utf16(name_len)?;
+        let model = block.take(4)?;
+        let color = match model {
+            b"RGB " => Color::new(
+                unit_to_u8(block.f32()?),
+                unit_to_u8(block.f32()?),
+                unit_to_u8(block.f32()?),
+            ),
+            b"CMYK" => cmyk_to_rgb(block.f32()?, block.f32()?, block.f32()?, block.f32()?),
+            b"Gray" => {
+                let gray = unit_to_u8(block.f32()?);
+                Color::new(gray, gray, gray)
+            }
+            // L 为 0~1，a、b 为 -128~127，白点 D50
+            b"LAB " => Lab::new(
+                block.f32()? * 100.0,
+                block.f32()?,
+                block.f32()?,
+                WhitePoint::D50,
+            )
+            .to_rgb(),
+            _ => {
+                let model = String::from_utf8_lossy(model).trim().to_string();
+                return Err(PaletteError::UnsupportedColorSpace(model));
+            }
+        };
+        palette.swatches.push(Swatch::new(name, color));
+    }
+    Ok(palette)
+}
+
+// 全部写成不分组的 RGB 普通颜色
+pub fn write(palette: &Palette) -> Vec<u8> {
+    let mut bytes = SIGNATURE.to_vec();
+    bytes.extend(1u16.to_be_bytes());
+    bytes.extend(0u16.to_be_bytes());
+    bytes.extend((palette.swatches.len() as u32).to_be_bytes());
+
+    for swatch in &palette.swatches {
+        let name = utf16_with_nul(&swatch.name);
+        let mut block = vec![];
+        block.extend((name.len() as u16).to_be_bytes());
+        push_utf16(&mut block, &name);
+        block.extend(b"RGB ");
+        for value in [swatch.color.r, swatch.color.g, swatch.color.b] {
+            block.extend((value as f32 / 255.0).to_be_bytes());
+        }
+        block.extend(NORMAL_COLOR.to_be_bytes());
+
+        bytes.extend(COLOR_ENTRY.to_be_bytes());
+        bytes.extend((block.len() as u32).to_be_bytes());
+        bytes.extend(block);
+    }
+    bytes
+}
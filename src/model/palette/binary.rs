@@ -0,0 +1,78 @@
+use crate::model::{color::Color, palette::PaletteError};
+
+// 大端序读取 ASE、ACO 这类二进制色板
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, position: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], PaletteError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .ok_or(PaletteError::UnexpectedEnd)?;
+        let bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(PaletteError::UnexpectedEnd)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub fn u16(&mut self) -> Result<u16, PaletteError> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, PaletteError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub fn f32(&mut self) -> Result<f32, PaletteError> {
+        Ok(f32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    // len 个 UTF-16 码元，末尾的 0 去掉
+    pub fn utf16(&mut self, len: usize) -> Result<String, PaletteError> {
+        let units: Vec<u16> = self
+            .take(len * 2)?
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        let units = units.strip_suffix(&[0]).unwrap_or(&units);
+        Ok(String::from_utf16_lossy(units))
+    }
+}
+
+// 以 0 结尾的 UTF-16 大端序字符串
+pub fn utf16_with_nul(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain([0]).collect()
+}
+
+pub fn push_utf16(bytes: &mut Vec<u8>, units: &[u16]) {
+    for unit in units {
+        bytes.extend(unit.to_be_bytes());
+    }
+}
+
+// 0~1 的通道值
+pub fn unit_to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+// 不考虑色彩管理的简单换算，各值为 0~1 的油墨量
+pub fn cmyk_to_rgb(c: f32, m: f32, y: f32, k: f32) -> Color {
+    Color::new(
+        unit_to_u8((1.0 - c) * (1.0 - k)),
+        unit_to_u8((1.0 - m) * (1.0 - k)),
+        unit_to_u8((1.0 - y) * (1.0 - k)),
+    )
+}
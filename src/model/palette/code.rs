@@ -0,0 +1,375 @@
+use std::collections::HashSet;
+
+use crate::model::{
+    color::Color,
+    palette::{Palette, json_string},
+};
+
+// 把色板导出为代码或设计令牌
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeFormat {
+    CSS,
+    SCSS,
+    Less,
+    Tailwind,
+    DesignTokens, // W3C Design Tokens 格式的 JSON
+    Android,
+    SwiftUI,
+    Egui,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Case {
+    Kebab,
+    Snake,
+    Camel,
+    ScreamingSnake,
+}
+
+impl CodeFormat {
+    pub const ALL: [CodeFormat; 8] = [
+        CodeFormat::CSS,
+        CodeFormat::SCSS,
+        CodeFormat::Less,
+        CodeFormat::Tailwind,
+        CodeFormat::DesignTokens,
+        CodeFormat::Android,
+        CodeFormat::SwiftUI,
+        CodeFormat::Egui,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CodeFormat::CSS => "CSS custom properties",
+            CodeFormat::SCSS => "SCSS variables",
+            CodeFormat::Less => "Less variables",
+            CodeFormat::Tailwind => "Tailwind theme.colors",
+            CodeFormat::DesignTokens => "Design Tokens JSON",
+            CodeFormat::Android => "Android colors.xml",
+            CodeFormat::SwiftUI => "SwiftUI Color",
+            CodeFormat::Egui => "egui Color32",
+        }
+    }
+
+    pub fn export(&self, palette: &Palette) -> String {
+        let names = swatch_words(palette);
+        let prefix = words(&palette.name);
+        // 变量是平铺的，名字前加上色板名
+        // Tailwind 和设计令牌按色板分组，组内的键可以是 500 这样的数字
+        let flat = |case: Case| -> Vec<(String, Color)> {
+            names
+                .iter()
+                .zip(&palette.swatches)
+                .map(|(name, swatch)| {
+                    let words: Vec<String> = prefix.iter().chain(name).cloned().collect();
+                    (identifier(&words, case), swatch.color)
+                })
+                .collect()
+        };
+
+        let mut text = String::new();
+        match self {
+            CodeFormat::CSS => {
+                text.push_str(":root {\n");
+                for (name, color) in flat(Case::Kebab) {
+                    text.push_str(&format!("  --{}: {};\n", name, color.to_hex_auto()));
+                }
+                text.push_str("}\n");
+            }
+            CodeFormat::SCSS | CodeFormat::Less => {
+                let sigil = if *self == CodeFormat::SCSS { '$' } else { '@' };
+                text.push_str(&format!("// {}\n", palette.name));
+                for (name, color) in flat(Case::Kebab) {
+                    text.push_str(&format!("{}{}: {};\n", sigil, name, color.to_hex_auto()));
+                }
+            }
+            CodeFormat::Tailwind => {
+                text.push_str("module.exports = {\n  theme: {\n    colors: {\n");
+                text.push_str(&format!(
+                    "      '{}': {{\n",
+                    identifier(&prefix, Case::Kebab)
+                ));
+                for (name, swatch) in names.iter().zip(&palette.swatches) {
+                    text.push_str(&format!(
+                        "        '{}': '{}',\n",
+                        name.join("-"),
+                        swatch.color.to_hex_auto()
+                    ));
+                }
+                text.push_str("      },\n    },\n  },\n}\n");
+            }
+            CodeFormat::DesignTokens => {
+                let group = json_string(&identifier(&prefix, Case::Kebab));
+                text.push_str(&format!("{{\n  {}: {{\n", group));
+                let tokens: Vec<String> = names
+                    .iter()
+                    .zip(&palette.swatches)
+                    .map(|(name, swatch)| {
+                        format!(
+                            "    {}: {{\n      \"$type\": \"color\",\n      \"$value\": {}\n    }}",
+                            json_string(&name.join("-")),
+                            json_string(&swatch.color.to_hex_auto())
+                        )
+                    })
+                    .collect();
+                text.push_str(&tokens.join(",\n"));
+                text.push_str("\n  }\n}\n");
+            }
+            CodeFormat::Android => {
+                text.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+                for (name, color) in flat(Case::Snake) {
+                    // Android 的不透明度在前
+                    let hex = if color.is_opaque() {
+                        color.to_hex()
+                    } else {
+                        color.to_hex_argb()
+                    };
+                    text.push_str(&format!("    <color name=\"{}\">{}</color>\n", name, hex));
+                }
+                text.push_str("</resources>\n");
+            }
+            CodeFormat::SwiftUI => {
+                text.push_str("import SwiftUI\n\nextension Color {\n");
+                for (name, color) in flat(Case::Camel) {
+                    let unit = |value: u8| format!("{:.3}", value as f32 / 255.0);
+                    let opacity = if color.is_opaque() {
+                        String::new()
+                    } else {
+                        format!(", opacity: {}", unit(color.a))
+                    };
+                    text.push_str(&format!(
+                        "    static let {} = Color(red: {}, green: {}, blue: {}{})\n",
+                        name,
+                        unit(color.r),
+                        unit(color.g),
+                        unit(color.b),
+                        opacity
+                    ));
+                }
+                text.push_str("}\n");
+            }
+            CodeFormat::Egui => {
+                text.push_str("use egui::Color32;\n\n");
+                for (name, color) in flat(Case::ScreamingSnake) {
+                    // from_rgba_unmultiplied 不是 const fn，半透明时写出预乘后的值
+                    let value = if color.is_opaque() {
+                        format!("Color32::from_rgb({}, {}, {})", color.r, color.g, color.b)
+                    } else {
+                        let [r, g, b, a] = color.to_color32().to_array();
+                        format!(
+                            "Color32::from_rgba_premultiplied({}, {}, {}, {})",
+                            r, g, b, a
+                        )
+                    };
+                    text.push_str(&format!("pub const {}: Color32 = {};\n", name, value));
+                }
+            }
+        }
+        text
+    }
+}
+
+// 名字中的 ASCII 字母和数字按其余字符断开，转成小写
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_lowercase())
+        .collect()
+}
+
+// 每个颜色的名字，没有可用字符或与前面重名时用序号
+fn swatch_words(palette: &Palette) -> Vec<Vec<String>> {
+    let mut used = HashSet::new();
+    palette
+        .swatches
+        .iter()
+        .enumerate()
+        .map(|(i, swatch)| {
+            let mut name = words(&swatch.name);
+            if name.is_empty() {
+                name = vec!["color".to_string(), (i + 1).to_string()];
+            }
+            if !used.insert(name.clone()) {
+                name.push((i + 1).to_string());
+                used.insert(name.clone());
+            }
+            name
+        })
+        .collect()
+}
+
+// 按命名风格拼接，不能以数字开头的语言在前面加上 color
+fn identifier(words: &[String], case: Case) -> String {
+    let mut words = words.to_vec();
+    if words
+        .first()
+        .is_none_or(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+    {
+        words.insert(0, "color".to_string());
+    }
+    match case {
+        Case::Kebab => words.join("-"),
+        Case::Snake => words.join("_"),
+        Case::ScreamingSnake => words.join("_").to_ascii_uppercase(),
+        Case::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                if i == 0 {
+                    return word.clone();
+                }
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        color::Color,
+        palette::{
+            Palette, Swatch,
+            code::{Case, CodeFormat, identifier, swatch_words, words},
+        },
+    };
+
+    fn palette(name: &str, swatches: &[(&str, Color)]) -> Palette {
+        let mut palette = Palette::new(name);
+        palette.swatches = swatches
+            .iter()
+            .map(|(name, color)| Swatch::new(*name, *color))
+            .collect();
+        palette
+    }
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn words_split_on_non_alphanumerics() {
+        assert_eq!(words("Sky Blue"), ["sky", "blue"]);
+        assert_eq!(words("  brand--500/Hover "), ["brand", "500", "hover"]);
+        assert_eq!(words("天空 blue"), ["blue"]);
+        assert!(words("").is_empty());
+        assert!(words("— ·").is_empty());
+    }
+
+    #[test]
+    fn swatch_words_number_empty_and_duplicate_names() {
+        let black = Color::new(0, 0, 0);
+        let palette = palette(
+            "p",
+            &[
+                ("", black),
+                ("Sky", black),
+                ("sky", black),
+                ("颜色", black),
+                ("sky 3", black),
+                ("color 1", black),
+            ],
+        );
+        let names: Vec<Vec<String>> = swatch_words(&palette);
+        let expected: Vec<Vec<String>> = [
+            &["color", "1"][..],
+            &["sky"],
+            &["sky", "3"],
+            &["color", "4"],
+            &["sky", "3", "5"],
+            &["color", "1", "6"],
+        ]
+        .iter()
+        .map(|words| strings(words))
+        .collect();
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn identifier_cases_and_digit_prefix() {
+        let name = strings(&["brand", "sky", "blue"]);
+        assert_eq!(identifier(&name, Case::Kebab), "brand-sky-blue");
+        assert_eq!(identifier(&name, Case::Snake), "brand_sky_blue");
+        assert_eq!(identifier(&name, Case::Camel), "brandSkyBlue");
+        assert_eq!(identifier(&name, Case::ScreamingSnake), "BRAND_SKY_BLUE");
+
+        // 不能以数字开头，空名字也补上 color
+        let name = strings(&["500", "a"]);
+        assert_eq!(identifier(&name, Case::Kebab), "color-500-a");
+        assert_eq!(identifier(&name, Case::Camel), "color500A");
+        assert_eq!(identifier(&name, Case::ScreamingSnake), "COLOR_500_A");
+        assert_eq!(identifier(&[], Case::Snake), "color");
+        assert_eq!(identifier(&strings(&["a500"]), Case::Snake), "a500");
+    }
+
+    #[test]
+    fn flat_formats_prefix_the_palette_name() {
+        let palette = palette("Brand", &[("Sky Blue", Color::new(0x87, 0xce, 0xeb))]);
+        let export = |format: CodeFormat| format.export(&palette);
+        assert_eq!(
+            export(CodeFormat::CSS),
+            ":root {\n  --brand-sky-blue: #87CEEB;\n}\n"
+        );
+        assert!(export(CodeFormat::SCSS).contains("$brand-sky-blue: #87CEEB;\n"));
+        assert!(export(CodeFormat::Less).contains("@brand-sky-blue: #87CEEB;\n"));
+        assert!(
+            export(CodeFormat::Android).contains("<color name=\"brand_sky_blue\">#87CEEB</color>")
+        );
+        assert!(
+            export(CodeFormat::SwiftUI).contains(
+                "static let brandSkyBlue = Color(red: 0.529, green: 0.808, blue: 0.922)\n"
+            )
+        );
+        assert!(
+            export(CodeFormat::Egui).contains(
+                "pub const BRAND_SKY_BLUE: Color32 = Color32::from_rgb(135, 206, 235);\n"
+            )
+        );
+    }
+
+    #[test]
+    fn grouped_formats_keep_numeric_keys() {
+        let palette = palette(
+            "",
+            &[
+                ("500", Color::new(0x33, 0x66, 0x99)),
+                ("", Color::new(0, 0, 0)),
+            ],
+        );
+        let tailwind = CodeFormat::Tailwind.export(&palette);
+        assert!(tailwind.contains("      'color': {\n"));
+        assert!(tailwind.contains("        '500': '#336699',\n        'color-2': '#000000',\n"));
+
+        let tokens: serde_json::Value =
+            serde_json::from_str(&CodeFormat::DesignTokens.export(&palette)).unwrap();
+        assert_eq!(tokens["color"]["500"]["$type"], "color");
+        assert_eq!(tokens["color"]["500"]["$value"], "#336699");
+        assert_eq!(tokens["color"]["color-2"]["$value"], "#000000");
+    }
+
+    #[test]
+    fn translucent_colors() {
+        let palette = palette("p", &[("glass", Color::new_rgba(0xff, 0x80, 0x00, 0x80))]);
+        let export = |format: CodeFormat| format.export(&palette);
+        assert!(export(CodeFormat::CSS).contains("--p-glass: #FF800080;"));
+        // Android 写成 #AARRGGBB
+        assert!(export(CodeFormat::Android).contains("<color name=\"p_glass\">#80FF8000</color>"));
+        assert!(
+            export(CodeFormat::SwiftUI)
+                .contains("Color(red: 1.000, green: 0.502, blue: 0.000, opacity: 0.502)")
+        );
+        // egui 常量写出预乘后的值
+        let [r, g, b, a] = Color::new_rgba(0xff, 0x80, 0x00, 0x80)
+            .to_color32()
+            .to_array();
+        assert!(r < 0xff && g < 0x80 && b == 0 && a == 0x80);
+        assert!(export(CodeFormat::Egui).contains(&format!(
+            "pub const P_GLASS: Color32 = Color32::from_rgba_premultiplied({}, {}, {}, 128);\n",
+            r, g, b
+        )));
+    }
+}
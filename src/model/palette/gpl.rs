@@ -0,0 +1,51 @@
+use crate::model::{
+    color::Color,
+    palette::{Palette, PaletteError, PaletteFormat, Swatch},
+};
+
+const HEADER: &str = "GIMP Palette";
+
+// 每行为 "R G B 名字"，# 开头的是注释，文件中的 Name: 作为色板名
+pub fn read(name: String, bytes: &[u8]) -> Result<Palette, PaletteError> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut lines = text.lines().enumerate();
+    if lines.next().map(|(_, line)| line.trim()) != Some(HEADER) {
+        return Err(PaletteError::InvalidHeader(PaletteFormat::GPL));
+    }
+
+    let mut palette = Palette::new(name);
+    for (i, line) in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Columns:") {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix("Name:") {
+            palette.name = name.trim().to_string();
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let rgb: Vec<u8> = fields
+            .iter()
+            .take(3)
+            .map_while(|field| field.parse().ok())
+            .collect();
+        let [r, g, b] = rgb[..] else {
+            return Err(PaletteError::InvalidLine(i + 1));
+        };
+        palette
+            .swatches
+            .push(Swatch::new(fields[3..].join(" "), Color::new(r, g, b)));
+    }
+    Ok(palette)
+}
+
+pub fn write(palette: &Palette) -> Vec<u8> {
+    let mut text = format!("{}\nName: {}\n#\n", HEADER, palette.name);
+    for swatch in &palette.swatches {
+        let Color { r, g, b, .. } = swatch.color;
+        let line = format!("{:3} {:3} {:3}\t{}", r, g, b, swatch.name);
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text.into_bytes()
+}
@@ -0,0 +1,29 @@
+use crate::model::{
+    color::Color,
+    palette::{Palette, PaletteError, Swatch},
+};
+
+// Lospec 的格式，每行一个不带 # 的 RRGGBB
+pub fn read(name: String, bytes: &[u8]) -> Result<Palette, PaletteError> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut palette = Palette::new(name);
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let color = Color::from_hex(line).map_err(|_| PaletteError::InvalidLine(i + 1))?;
+        palette.swatches.push(Swatch::new("", color));
+    }
+    Ok(palette)
+}
+
+pub fn write(palette: &Palette) -> Vec<u8> {
+    let mut text = String::new();
+    for swatch in &palette.swatches {
+        let hex = swatch.color.to_hex().to_lowercase();
+        text.push_str(hex.trim_start_matches('#'));
+        text.push('\n');
+    }
+    text.into_bytes()
+}
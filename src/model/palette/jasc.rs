@@ -0,0 +1,54 @@
+use crate::model::{
+    color::Color,
+    palette::{Palette, PaletteError, PaletteFormat, Swatch},
+};
+
+const HEADER: &str = "JASC-PAL";
+const VERSION: &str = "0100";
+
+// 文件头、版本、颜色数，之后每行 "R G B"，Aseprite 可能多一列不透明度
+pub fn read(name: String, bytes: &[u8]) -> Result<Palette, PaletteError> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut lines = text.lines().map(str::trim).enumerate();
+    if lines.next().map(|(_, line)| line) != Some(HEADER) {
+        return Err(PaletteError::InvalidHeader(PaletteFormat::JASC));
+    }
+    let _version = lines.next().ok_or(PaletteError::UnexpectedEnd)?;
+    let count: usize = match lines.next() {
+        Some((_, line)) => line.parse().map_err(|_| PaletteError::InvalidLine(3))?,
+        None => return Err(PaletteError::UnexpectedEnd),
+    };
+
+    let mut palette = Palette::new(name);
+    for (i, line) in lines.filter(|(_, line)| !line.is_empty()).take(count) {
+        let values: Vec<u8> = line
+            .split_whitespace()
+            .map(|field| field.parse().map_err(|_| PaletteError::InvalidLine(i + 1)))
+            .collect::<Result<_, _>>()?;
+        let color = match values[..] {
+            [r, g, b] => Color::new(r, g, b),
+            [r, g, b, a] => Color::new_rgba(r, g, b, a),
+            _ => return Err(PaletteError::InvalidLine(i + 1)),
+        };
+        palette.swatches.push(Swatch::new("", color));
+    }
+    if palette.swatches.len() < count {
+        return Err(PaletteError::UnexpectedEnd);
+    }
+    Ok(palette)
+}
+
+// 没有名字，也不写不透明度
+pub fn write(palette: &Palette) -> Vec<u8> {
+    let mut text = format!(
+        "{}\r\n{}\r\n{}\r\n",
+        HEADER,
+        VERSION,
+        palette.swatches.len()
+    );
+    for swatch in &palette.swatches {
+        let Color { r, g, b, .. } = swatch.color;
+        text.push_str(&format!("{} {} {}\r\n", r, g, b));
+    }
+    text.into_bytes()
+}
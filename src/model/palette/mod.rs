@@ -0,0 +1,428 @@
+use std::{error::Error, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::color::Color;
+
+pub mod aco;
+pub mod ase;
+mod binary;
+pub mod code;
+pub mod gpl;
+pub mod hex;
+pub mod jasc;
+pub mod terminal;
+
+// 色板中的一个颜色，名字可以为空
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Swatch {
+    #[serde(default)]
+    pub name: String,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Palette {
+    pub name: String,
+    #[serde(default)]
+    pub swatches: Vec<Swatch>,
+}
+
+// 用户的全部色板，按显示顺序排列
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaletteCollection {
+    palettes: Vec<Palette>,
+    #[serde(skip)]
+    dirty: bool, // 有未保存的修改
+}
+
+#[derive(Debug)]
+pub enum PaletteError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    UnsupportedFormat(String),
+    InvalidHeader(PaletteFormat),
+    UnexpectedEnd,
+    InvalidLine(usize),
+    UnsupportedColorSpace(String),
+    ColorCount(usize), // 终端配色需要 16~21 个颜色
+}
+
+// 其他软件的色板文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteFormat {
+    ASE,  // Adobe Swatch Exchange，Illustrator、InDesign
+    ACO,  // Photoshop
+    GPL,  // GIMP、Inkscape、Krita
+    JASC, // Paint Shop Pro、Aseprite
+    Hex,  // Lospec，每行一个 hex
+}
+
+impl Display for PaletteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaletteError::Io(e) => write!(f, "unable to access palettes: {}", e),
+            PaletteError::Json(e) => write!(f, "invalid palette file: {}", e),
+            PaletteError::UnsupportedFormat(ext) => {
+                write!(f, "unsupported palette format: {}", ext)
+            }
+            PaletteError::InvalidHeader(format) => write!(f, "not a {} file", format.name()),
+            PaletteError::UnexpectedEnd => write!(f, "palette file ends unexpectedly"),
+            PaletteError::InvalidLine(line) => write!(f, "invalid color on line {}", line),
+            PaletteError::UnsupportedColorSpace(space) => {
+                write!(f, "unsupported color space: {}", space)
+            }
+            PaletteError::ColorCount(count) => {
+                write!(f, "terminal schemes need 16 to 21 colors, found {}", count)
+            }
+        }
+    }
+}
+
+impl Error for PaletteError {}
+
+impl Swatch {
+    pub fn new(name: impl Into<String>, color: Color) -> Self {
+        Swatch {
+            name: name.into(),
+            color,
+        }
+    }
+}
+
+impl Display for Swatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.name.is_empty() {
+            write!(f, "{}", self.color.to_hex_auto())
+        } else {
+            write!(f, "{} {}", self.name, self.color.to_hex_auto())
+        }
+    }
+}
+
+impl PaletteFormat {
+    pub const ALL: [PaletteFormat; 5] = [
+        PaletteFormat::ASE,
+        PaletteFormat::ACO,
+        PaletteFormat::GPL,
+        PaletteFormat::JASC,
+        PaletteFormat::Hex,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PaletteFormat::ASE => "Adobe Swatch Exchange",
+            PaletteFormat::ACO => "Photoshop swatches",
+            PaletteFormat::GPL => "GIMP palette",
+            PaletteFormat::JASC => "JASC palette",
+            PaletteFormat::Hex => "hex list",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PaletteFormat::ASE => "ase",
+            PaletteFormat::ACO => "aco",
+            PaletteFormat::GPL => "gpl",
+            PaletteFormat::JASC => "pal",
+            PaletteFormat::Hex => "hex",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let extension = extension.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extension() == extension)
+    }
+
+    // name 为文件中没有色板名时使用的名字
+    pub fn read(&self, name: impl Into<String>, bytes: &[u8]) -> Result<Palette, PaletteError> {
+        let name = name.into();
+        match self {
+            PaletteFormat::ASE => ase::read(name, bytes),
+            PaletteFormat::ACO => aco::read(name, bytes),
+            PaletteFormat::GPL => gpl::read(name, bytes),
+            PaletteFormat::JASC => jasc::read(name, bytes),
+            PaletteFormat::Hex => hex::read(name, bytes),
+        }
+    }
+
+    // 格式不支持的信息（名字、不透明度）直接丢弃
+    pub fn write(&self, palette: &Palette) -> Vec<u8> {
+        match self {
+            PaletteFormat::ASE => ase::write(palette),
+            PaletteFormat::ACO => aco::write(palette),
+            PaletteFormat::GPL => gpl::write(palette),
+            PaletteFormat::JASC => jasc::write(palette),
+            PaletteFormat::Hex => hex::write(palette),
+        }
+    }
+}
+
+impl Palette {
+    pub fn new(name: impl Into<String>) -> Self {
+        Palette {
+            name: name.into(),
+            swatches: vec![],
+        }
+    }
+}
+
+impl PaletteCollection {
+    pub fn new() -> Self {
+        PaletteCollection::default()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, PaletteError> {
+        serde_json::from_str(json).map_err(PaletteError::Json)
+    }
+
+    pub fn to_json(&self) -> Result<String, PaletteError> {
+        serde_json::to_string_pretty(self).map_err(PaletteError::Json)
+    }
+
+    pub fn palettes(&self) -> &[Palette] {
+        &self.palettes
+    }
+
+    // 新建一个空色板，名字为 "palette N" 中没有用过的，返回其位置
+    pub fn create(&mut self) -> usize {
+        let name = (1..)
+            .map(|n| format!("palette {}", n))
+            .find(|name| self.palettes.iter().all(|p| &p.name != name))
+            .unwrap_or_default();
+        self.palettes.push(Palette::new(name));
+        self.dirty = true;
+        self.palettes.len() - 1
+    }
+
+    // 加入导入的色板，返回其位置
+    pub fn push(&mut self, palette: Palette) -> usize {
+        self.palettes.push(palette);
+        self.dirty = true;
+        self.palettes.len() - 1
+    }
+
+    pub fn rename(&mut self, index: usize, name: impl Into<String>) {
+        if let Some(palette) = self.palettes.get_mut(index) {
+            palette.name = name.into();
+            self.dirty = true;
+        }
+    }
+
+    // 把第 from 个色板移到 to 的位置
+    pub fn move_palette(&mut self, from: usize, to: usize) {
+        if from < self.palettes.len() && to < self.palettes.len() && from != to {
+            let palette = self.palettes.remove(from);
+            self.palettes.insert(to, palette);
+            self.dirty = true;
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.palettes.len() {
+            self.palettes.remove(index);
+            self.dirty = true;
+        }
+    }
+
+    // 色板中已有相同的颜色时不重复加入
+    pub fn add_swatch(&mut self, index: usize, swatch: Swatch) {
+        if let Some(palette) = self.palettes.get_mut(index)
+            && palette.swatches.iter().all(|s| s.color != swatch.color)
+        {
+            palette.swatches.push(swatch);
+            self.dirty = true;
+        }
+    }
+
+    pub fn rename_swatch(&mut self, index: usize, swatch: usize, name: impl Into<String>) {
+        if let Some(swatch) = self
+            .palettes
+            .get_mut(index)
+            .and_then(|p| p.swatches.get_mut(swatch))
+        {
+            swatch.name = name.into();
+            self.dirty = true;
+        }
+    }
+
+    // 在第 index 个色板中把颜色从 from 移到 to
+    pub fn move_swatch(&mut self, index: usize, from: usize, to: usize) {
+        if let Some(palette) = self.palettes.get_mut(index)
+            && from < palette.swatches.len()
+            && to < palette.swatches.len()
+            && from != to
+        {
+            let swatch = palette.swatches.remove(from);
+            palette.swatches.insert(to, swatch);
+            self.dirty = true;
+        }
+    }
+
+    pub fn remove_swatch(&mut self, index: usize, swatch: usize) {
+        if let Some(palette) = self.palettes.get_mut(index)
+            && swatch < palette.swatches.len()
+        {
+            palette.swatches.remove(swatch);
+            self.dirty = true;
+        }
+    }
+
+    // 返回是否有未保存的修改，并清除标记
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+}
+
+// 导出为 JSON 格式的代码和终端配色时给字符串加引号并转义
+fn json_string(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        color::Color,
+        palette::{Palette, PaletteCollection, PaletteError, Swatch},
+    };
+
+    fn names(palettes: &PaletteCollection) -> Vec<&str> {
+        palettes
+            .palettes()
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect()
+    }
+
+    fn swatches(palettes: &PaletteCollection, index: usize) -> Vec<(&str, u8)> {
+        palettes.palettes()[index]
+            .swatches
+            .iter()
+            .map(|s| (s.name.as_str(), s.color.r))
+            .collect()
+    }
+
+    fn red(value: u8) -> Color {
+        Color::new(value, 0, 0)
+    }
+
+    #[test]
+    fn create_rename_move_and_remove_palettes() {
+        let mut palettes = PaletteCollection::new();
+        assert!(!palettes.take_dirty());
+        assert_eq!(palettes.create(), 0);
+        assert_eq!(palettes.create(), 1);
+        assert!(palettes.take_dirty());
+
+        // 新名字跳过已用的
+        palettes.rename(0, "palette 3");
+        assert_eq!(palettes.create(), 2);
+        assert_eq!(names(&palettes), ["palette 3", "palette 2", "palette 1"]);
+        assert_eq!(palettes.push(Palette::new("imported")), 3);
+
+        palettes.move_palette(3, 0);
+        assert_eq!(
+            names(&palettes),
+            ["imported", "palette 3", "palette 2", "palette 1"]
+        );
+        palettes.move_palette(0, 2);
+        assert_eq!(
+            names(&palettes),
+            ["palette 3", "palette 2", "imported", "palette 1"]
+        );
+        palettes.remove(1);
+        assert_eq!(names(&palettes), ["palette 3", "imported", "palette 1"]);
+        assert!(palettes.take_dirty());
+    }
+
+    #[test]
+    fn out_of_range_operations_change_nothing() {
+        let mut palettes = PaletteCollection::new();
+        palettes.create();
+        palettes.add_swatch(0, Swatch::new("a", red(1)));
+        palettes.take_dirty();
+        let before = palettes.clone();
+
+        palettes.rename(1, "x");
+        palettes.move_palette(0, 1);
+        palettes.move_palette(0, 0);
+        palettes.remove(1);
+        palettes.add_swatch(1, Swatch::new("b", red(2)));
+        palettes.rename_swatch(0, 1, "x");
+        palettes.rename_swatch(1, 0, "x");
+        palettes.move_swatch(0, 0, 1);
+        palettes.move_swatch(1, 0, 0);
+        palettes.remove_swatch(0, 1);
+        palettes.remove_swatch(1, 0);
+
+        assert_eq!(palettes, before);
+        assert!(!palettes.take_dirty());
+    }
+
+    #[test]
+    fn add_rename_move_and_remove_swatches() {
+        let mut palettes = PaletteCollection::new();
+        palettes.create();
+        palettes.add_swatch(0, Swatch::new("", red(1)));
+        palettes.add_swatch(0, Swatch::new("two", red(2)));
+        palettes.add_swatch(0, Swatch::new("", red(3)));
+        assert_eq!(swatches(&palettes, 0), [("", 1), ("two", 2), ("", 3)]);
+
+        palettes.rename_swatch(0, 0, "one");
+        palettes.move_swatch(0, 2, 0);
+        assert_eq!(swatches(&palettes, 0), [("", 3), ("one", 1), ("two", 2)]);
+        palettes.move_swatch(0, 0, 2);
+        assert_eq!(swatches(&palettes, 0), [("one", 1), ("two", 2), ("", 3)]);
+        palettes.remove_swatch(0, 1);
+        assert_eq!(swatches(&palettes, 0), [("one", 1), ("", 3)]);
+        assert!(palettes.take_dirty());
+    }
+
+    #[test]
+    fn adding_an_existing_color_is_ignored() {
+        let mut palettes = PaletteCollection::new();
+        palettes.create();
+        palettes.create();
+        palettes.add_swatch(0, Swatch::new("first", red(1)));
+        palettes.take_dirty();
+
+        palettes.add_swatch(0, Swatch::new("again", red(1)));
+        assert_eq!(swatches(&palettes, 0), [("first", 1)]);
+        assert!(!palettes.take_dirty());
+
+        // 不透明度不同算不同的颜色，其他色板不受影响
+        palettes.add_swatch(0, Swatch::new("", Color::new_rgba(1, 0, 0, 128)));
+        palettes.add_swatch(1, Swatch::new("", red(1)));
+        assert_eq!(palettes.palettes()[0].swatches.len(), 2);
+        assert_eq!(swatches(&palettes, 1), [("", 1)]);
+        assert!(palettes.take_dirty());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut palettes = PaletteCollection::new();
+        let index = palettes.create();
+        palettes.add_swatch(index, Swatch::new("sky", Color::new(135, 206, 235)));
+        palettes.add_swatch(index, Swatch::new("", Color::new_rgba(1, 2, 3, 4)));
+        palettes.push(Palette::new("empty"));
+
+        let json = palettes.to_json().unwrap();
+        assert!(!json.contains("dirty"));
+        let loaded = PaletteCollection::from_json(&json).unwrap();
+        assert_eq!(loaded.palettes(), palettes.palettes());
+
+        // 没有名字和颜色列表时用默认值
+        let loaded = PaletteCollection::from_json(
+            r##"{"palettes": [{"name": "a"}, {"name": "b", "swatches": [{"color": "#010203"}]}]}"##,
+        )
+        .unwrap();
+        assert!(loaded.palettes()[0].swatches.is_empty());
+        assert_eq!(loaded.palettes()[1].swatches[0].name, "");
+        assert!(matches!(
+            PaletteCollection::from_json("[]"),
+            Err(PaletteError::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,274 @@
+use crate::model::{
+    color::{Color, ansi::ANSI_NAMES},
+    palette::{Palette, PaletteError, json_string},
+};
+
+// 把色板导出为终端配色，颜色按顺序为 ANSI 0~15，
+// 之后可选前景、背景、光标、选区背景、选区前景
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalFormat {
+    Alacritty,
+    WezTerm,
+    WindowsTerminal,
+    Kitty,
+    Xresources,
+}
+
+// 终端配色的各个颜色，缺少的特殊颜色从 16 色中补上
+struct Scheme {
+    ansi: [Color; 16],
+    foreground: Color,
+    background: Color,
+    cursor: Color,
+    selection_background: Color,
+    selection_foreground: Color,
+}
+
+impl Scheme {
+    const MIN_COLORS: usize = 16;
+    const MAX_COLORS: usize = 21;
+
+    fn new(palette: &Palette) -> Result<Self, PaletteError> {
+        let colors: Vec<Color> = palette.swatches.iter().map(|s| s.color).collect();
+        if !(Self::MIN_COLORS..=Self::MAX_COLORS).contains(&colors.len()) {
+            return Err(PaletteError::ColorCount(colors.len()));
+        }
+        let ansi: [Color; 16] = colors[..16].try_into().unwrap();
+        let foreground = colors.get(16).copied().unwrap_or(ansi[7]);
+        let background = colors.get(17).copied().unwrap_or(ansi[0]);
+        Ok(Scheme {
+            ansi,
+            foreground,
+            background,
+            cursor: colors.get(18).copied().unwrap_or(foreground),
+            selection_background: colors.get(19).copied().unwrap_or(ansi[8]),
+            selection_foreground: colors.get(20).copied().unwrap_or(foreground),
+        })
+    }
+}
+
+impl TerminalFormat {
+    pub const ALL: [TerminalFormat; 5] = [
+        TerminalFormat::Alacritty,
+        TerminalFormat::WezTerm,
+        TerminalFormat::WindowsTerminal,
+        TerminalFormat::Kitty,
+        TerminalFormat::Xresources,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TerminalFormat::Alacritty => "Alacritty TOML",
+            TerminalFormat::WezTerm => "WezTerm TOML",
+            TerminalFormat::WindowsTerminal => "Windows Terminal JSON",
+            TerminalFormat::Kitty => "kitty.conf",
+            TerminalFormat::Xresources => "Xresources",
+        }
+    }
+
+    // 色板可以导出为终端配色
+    pub fn accepts(palette: &Palette) -> bool {
+        (Scheme::MIN_COLORS..=Scheme::MAX_COLORS).contains(&palette.swatches.len())
+    }
+
+    pub fn export(&self, palette: &Palette) -> Result<String, PaletteError> {
+        let scheme = Scheme::new(palette)?;
+        let hex = |color: Color| color.to_hex();
+        let quoted = |color: Color| format!("\"{}\"", color.to_hex());
+        let mut text = String::new();
+        match self {
+            TerminalFormat::Alacritty => {
+                text.push_str(&format!(
+                    "[colors.primary]\nforeground = {}\nbackground = {}\n\n",
+                    quoted(scheme.foreground),
+                    quoted(scheme.background)
+                ));
+                text.push_str(&format!(
+                    "[colors.cursor]\ntext = {}\ncursor = {}\n\n",
+                    quoted(scheme.background),
+                    quoted(scheme.cursor)
+                ));
+                text.push_str(&format!(
+                    "[colors.selection]\ntext = {}\nbackground = {}\n",
+                    quoted(scheme.selection_foreground),
+                    quoted(scheme.selection_background)
+                ));
+                for (section, colors) in
+                    [("normal", &scheme.ansi[..8]), ("bright", &scheme.ansi[8..])]
+                {
+                    text.push_str(&format!("\n[colors.{}]\n", section));
+                    for (name, color) in ANSI_NAMES.iter().zip(colors) {
+                        text.push_str(&format!("{} = {}\n", name, quoted(*color)));
+                    }
+                }
+            }
+            TerminalFormat::WezTerm => {
+                let list = |colors: &[Color]| {
+                    let items: Vec<String> = colors.iter().map(|c| quoted(*c)).collect();
+                    format!("[{}]", items.join(", "))
+                };
+                text.push_str("[colors]\n");
+                for (key, color) in [
+                    ("foreground", scheme.foreground),
+                    ("background", scheme.background),
+                    ("cursor_bg", scheme.cursor),
+                    ("cursor_border", scheme.cursor),
+                    ("cursor_fg", scheme.background),
+                    ("selection_bg", scheme.selection_background),
+                    ("selection_fg", scheme.selection_foreground),
+                ] {
+                    text.push_str(&format!("{} = {}\n", key, quoted(color)));
+                }
+                text.push_str(&format!("ansi = {}\n", list(&scheme.ansi[..8])));
+                text.push_str(&format!("brights = {}\n", list(&scheme.ansi[8..])));
+                text.push_str(&format!(
+                    "\n[metadata]\nname = {}\n",
+                    json_string(&palette.name)
+                ));
+            }
+            TerminalFormat::WindowsTerminal => {
+                // Windows Terminal 把 magenta 叫做 purple
+                let names = ANSI_NAMES.map(|name| if name == "magenta" { "purple" } else { name });
+                let mut entries = vec![
+                    format!("\"name\": {}", json_string(&palette.name)),
+                    format!("\"foreground\": {}", quoted(scheme.foreground)),
+                    format!("\"background\": {}", quoted(scheme.background)),
+                    format!("\"cursorColor\": {}", quoted(scheme.cursor)),
+                    format!(
+                        "\"selectionBackground\": {}",
+                        quoted(scheme.selection_background)
+                    ),
+                ];
+                for (i, color) in scheme.ansi.iter().enumerate() {
+                    let name = names[i % 8];
+                    let key = if i < 8 {
+                        name.to_string()
+                    } else {
+                        format!("bright{}{}", name[..1].to_uppercase(), &name[1..])
+                    };
+                    entries.push(format!("\"{}\": {}", key, quoted(*color)));
+                }
+                text.push_str(&format!("{{\n    {}\n}}\n", entries.join(",\n    ")));
+            }
+            TerminalFormat::Kitty => {
+                for (key, color) in [
+                    ("foreground", scheme.foreground),
+                    ("background", scheme.background),
+                    ("cursor", scheme.cursor),
+                    ("cursor_text_color", scheme.background),
+                    ("selection_foreground", scheme.selection_foreground),
+                    ("selection_background", scheme.selection_background),
+                ] {
+                    text.push_str(&format!("{} {}\n", key, hex(color)));
+                }
+                for (i, color) in scheme.ansi.iter().enumerate() {
+                    text.push_str(&format!("color{} {}\n", i, hex(*color)));
+                }
+            }
+            TerminalFormat::Xresources => {
+                text.push_str(&format!("! {}\n", palette.name));
+                for (key, color) in [
+                    ("foreground", scheme.foreground),
+                    ("background", scheme.background),
+                    ("cursorColor", scheme.cursor),
+                ] {
+                    text.push_str(&format!("*.{}: {}\n", key, hex(color)));
+                }
+                for (i, color) in scheme.ansi.iter().enumerate() {
+                    text.push_str(&format!("*.color{}: {}\n", i, hex(*color)));
+                }
+            }
+        }
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        color::Color,
+        palette::{
+            Palette, PaletteError, Swatch,
+            terminal::{Scheme, TerminalFormat},
+        },
+    };
+
+    // 第 i 个颜色为 (i, i, i)
+    fn palette(count: usize) -> Palette {
+        let mut palette = Palette::new("scheme");
+        palette.swatches = (0..count).map(|i| Swatch::new("", gray(i))).collect();
+        palette
+    }
+
+    fn gray(i: usize) -> Color {
+        Color::new(i as u8, i as u8, i as u8)
+    }
+
+    #[test]
+    fn color_count_bounds() {
+        for count in [0, 15, 22] {
+            assert!(matches!(
+                Scheme::new(&palette(count)),
+                Err(PaletteError::ColorCount(n)) if n == count
+            ));
+            assert!(!TerminalFormat::accepts(&palette(count)));
+            for format in TerminalFormat::ALL {
+                assert!(format.export(&palette(count)).is_err());
+            }
+        }
+        for count in 16..=21 {
+            assert!(Scheme::new(&palette(count)).is_ok());
+            assert!(TerminalFormat::accepts(&palette(count)));
+        }
+    }
+
+    #[test]
+    fn missing_special_colors_fall_back() {
+        let scheme = Scheme::new(&palette(16)).unwrap();
+        assert_eq!(scheme.ansi, std::array::from_fn(gray));
+        assert_eq!(scheme.foreground, gray(7));
+        assert_eq!(scheme.background, gray(0));
+        assert_eq!(scheme.cursor, gray(7));
+        assert_eq!(scheme.selection_background, gray(8));
+        assert_eq!(scheme.selection_foreground, gray(7));
+
+        // 光标和选区前景跟随给出的前景色
+        let scheme = Scheme::new(&palette(18)).unwrap();
+        assert_eq!(scheme.foreground, gray(16));
+        assert_eq!(scheme.background, gray(17));
+        assert_eq!(scheme.cursor, gray(16));
+        assert_eq!(scheme.selection_background, gray(8));
+        assert_eq!(scheme.selection_foreground, gray(16));
+
+        let scheme = Scheme::new(&palette(21)).unwrap();
+        assert_eq!(scheme.cursor, gray(18));
+        assert_eq!(scheme.selection_background, gray(19));
+        assert_eq!(scheme.selection_foreground, gray(20));
+    }
+
+    #[test]
+    fn exports_name_every_color() {
+        let palette = palette(21);
+        let alacritty = TerminalFormat::Alacritty.export(&palette).unwrap();
+        assert!(
+            alacritty
+                .contains("[colors.primary]\nforeground = \"#101010\"\nbackground = \"#111111\"")
+        );
+        assert!(alacritty.contains("[colors.bright]\nblack = \"#080808\""));
+        let wezterm = TerminalFormat::WezTerm.export(&palette).unwrap();
+        assert!(wezterm.contains("cursor_bg = \"#121212\""));
+        assert!(wezterm.contains("name = \"scheme\""));
+        let windows: serde_json::Value =
+            serde_json::from_str(&TerminalFormat::WindowsTerminal.export(&palette).unwrap())
+                .unwrap();
+        assert_eq!(windows["purple"], "#050505");
+        assert_eq!(windows["brightWhite"], "#0F0F0F");
+        assert_eq!(windows["selectionBackground"], "#131313");
+        let kitty = TerminalFormat::Kitty.export(&palette).unwrap();
+        assert!(kitty.contains("selection_foreground #141414\n"));
+        assert!(kitty.contains("color15 #0F0F0F\n"));
+        let xresources = TerminalFormat::Xresources.export(&palette).unwrap();
+        assert!(xresources.starts_with("! scheme\n*.foreground: #101010\n"));
+        assert!(xresources.ends_with("*.color15: #0F0F0F\n"));
+    }
+}
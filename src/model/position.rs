@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
@@ -12,6 +14,7 @@ impl Display for Position {
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<String> for Position {
     fn into(self) -> String {
         format!("x:{:} y:{:}", self.x, self.y)
@@ -0,0 +1,109 @@
+use crate::model::color::{
+    Color,
+    ansi::{ansi16_escape, xterm256_escape},
+    cie::WhitePoint,
+};
+
+// 主面板的读数行，RGB/Hex/HSV/HSL 总是显示，其余可选，显示在 HSV/HSL 下面
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Readout {
+    RGB,
+    #[default]
+    Hex,
+    HSV,
+    HSL,
+    XYZ,
+    Lab,
+    LCh,
+    LabD50,
+    LChD50,
+    OKLab,
+    OKLCh,
+    Xterm256, // 最接近的 xterm 256 色序号和转义序列
+    ANSI16,
+}
+
+impl Readout {
+    pub const FIXED: [Readout; 4] = [Readout::RGB, Readout::Hex, Readout::HSV, Readout::HSL];
+
+    pub const OPTIONAL: [Readout; 9] = [
+        Readout::XYZ,
+        Readout::Lab,
+        Readout::LCh,
+        Readout::LabD50,
+        Readout::LChD50,
+        Readout::OKLab,
+        Readout::OKLCh,
+        Readout::Xterm256,
+        Readout::ANSI16,
+    ];
+
+    // 命令行中使用的名字
+    pub fn key(&self) -> &'static str {
+        match self {
+            Readout::RGB => "rgb",
+            Readout::Hex => "hex",
+            Readout::HSV => "hsv",
+            Readout::HSL => "hsl",
+            Readout::XYZ => "xyz",
+            Readout::Lab => "lab",
+            Readout::LCh => "lch",
+            Readout::LabD50 => "lab-d50",
+            Readout::LChD50 => "lch-d50",
+            Readout::OKLab => "oklab",
+            Readout::OKLCh => "oklch",
+            Readout::Xterm256 => "xterm",
+            Readout::ANSI16 => "ansi",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        let key = key.to_lowercase();
+        Self::FIXED
+            .into_iter()
+            .chain(Self::OPTIONAL)
+            .find(|readout| readout.key() == key)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Readout::RGB => "RGB",
+            Readout::Hex => "Hex",
+            Readout::HSV => "HSV",
+            Readout::HSL => "HSL",
+            Readout::XYZ => "XYZ (D65)",
+            Readout::Lab => "Lab (D65)",
+            Readout::LCh => "LCh (D65)",
+            Readout::LabD50 => "Lab (D50)",
+            Readout::LChD50 => "LCh (D50)",
+            Readout::OKLab => "OKLab",
+            Readout::OKLCh => "OKLCh",
+            Readout::Xterm256 => "xterm-256",
+            Readout::ANSI16 => "ANSI-16",
+        }
+    }
+
+    pub fn format(&self, color: &Color) -> String {
+        match self {
+            Readout::RGB => color.to_string(),
+            Readout::Hex => color.to_hex_auto(),
+            Readout::HSV => color.to_hsv().into(),
+            Readout::HSL => color.to_hsl().into(),
+            Readout::XYZ => color.to_xyz().into(),
+            Readout::Lab => color.to_lab(WhitePoint::D65).into(),
+            Readout::LCh => color.to_lch(WhitePoint::D65).into(),
+            Readout::LabD50 => color.to_lab(WhitePoint::D50).into(),
+            Readout::LChD50 => color.to_lch(WhitePoint::D50).into(),
+            Readout::OKLab => color.to_oklab().to_css(),
+            Readout::OKLCh => color.to_oklch().to_css(),
+            Readout::Xterm256 => {
+                let index = color.nearest_xterm256();
+                format!("xterm {} {}", index, xterm256_escape(index))
+            }
+            Readout::ANSI16 => {
+                let index = color.nearest_ansi16();
+                format!("ansi {} {}", index, ansi16_escape(index))
+            }
+        }
+    }
+}
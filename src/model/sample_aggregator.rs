@@ -0,0 +1,378 @@
+use std::{collections::HashMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::color::Color;
+
+// 采样范围内的像素合成一个颜色的方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleAggregator {
+    #[default]
+    Mean,
+    Median,       // 每个通道分别取中位数
+    VectorMedian, // 到其余像素距离之和最小的像素
+    Mode,         // 出现最多的颜色
+    TrimmedMean,  // 每个通道去掉两端各 TRIM 的权重后取平均
+}
+
+// 采样的一个像素，四个通道和形状给出的权重
+pub type Sample = ([f32; 4], f32);
+
+impl SampleAggregator {
+    pub const ALL: [SampleAggregator; 5] = [
+        SampleAggregator::Mean,
+        SampleAggregator::Median,
+        SampleAggregator::VectorMedian,
+        SampleAggregator::Mode,
+        SampleAggregator::TrimmedMean,
+    ];
+
+    pub const TRIM: f32 = 0.2;
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SampleAggregator::Mean => "mean",
+            SampleAggregator::Median => "median",
+            SampleAggregator::VectorMedian => "vector median",
+            SampleAggregator::Mode => "mode",
+            SampleAggregator::TrimmedMean => "trimmed mean",
+        }
+    }
+
+    // 权重为 0 的像素应事先去掉，samples 为空时返回 0
+    pub fn aggregate(&self, samples: &[Sample]) -> [f32; 4] {
+        if samples.is_empty() {
+            return [0.0; 4];
+        }
+        match self {
+            SampleAggregator::Mean => weighted_mean(samples),
+            SampleAggregator::Median => per_channel(samples, |values| trimmed_mean(values, 0.5)),
+            SampleAggregator::VectorMedian => vector_median(samples),
+            SampleAggregator::Mode => mode(samples),
+            SampleAggregator::TrimmedMean => {
+                per_channel(samples, |values| trimmed_mean(values, Self::TRIM))
+            }
+        }
+    }
+}
+
+fn weighted_mean(samples: &[Sample]) -> [f32; 4] {
+    let total = samples.iter().map(|(_, w)| w).sum::<f32>();
+    let mut mean = [0.0; 4];
+    for (value, weight) in samples {
+        for i in 0..4 {
+            mean[i] += value[i] * weight / total;
+        }
+    }
+    mean
+}
+
+// 把每个通道的 (值, 权重) 按值排序后交给 f
+fn per_channel(samples: &[Sample], f: impl Fn(&[(f32, f32)]) -> f32) -> [f32; 4] {
+    let mut result = [0.0; 4];
+    for (i, channel) in result.iter_mut().enumerate() {
+        let mut values: Vec<(f32, f32)> = samples.iter().map(|(v, w)| (v[i], *w)).collect();
+        values.sort_by(|a, b| a.0.total_cmp(&b.0));
+        *channel = f(&values);
+    }
+    result
+}
+
+// 在累计权重上去掉两端各 trim 的部分后加权平均
+// trim 为 0.5 时只剩中间一点，即加权中位数，偶数个等权样本时为中间两个的平均
+fn trimmed_mean(sorted: &[(f32, f32)], trim: f32) -> f32 {
+    let total = sorted.iter().map(|(_, w)| w).sum::<f32>();
+    let (low, high) = (total * trim, total * (1.0 - trim));
+    if high - low <= total * 1e-4 {
+        let middle = (low + high) / 2.0;
+        let window = total * 1e-4;
+        return trimmed_window(sorted, middle - window, middle + window);
+    }
+    trimmed_window(sorted, low, high)
+}
+
+fn trimmed_window(sorted: &[(f32, f32)], low: f32, high: f32) -> f32 {
+    let (mut sum, mut kept, mut start) = (0.0, 0.0, 0.0);
+    for (value, weight) in sorted {
+        let end = start + weight;
+        let overlap = end.min(high) - start.max(low);
+        if overlap > 0.0 {
+            sum += value * overlap;
+            kept += overlap;
+        }
+        start = end;
+    }
+    if kept > 0.0 { sum / kept } else { sorted[0].0 }
+}
+
+fn vector_median(samples: &[Sample]) -> [f32; 4] {
+    let distance = |a: &[f32; 4], b: &[f32; 4]| {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<f32>()
+            .sqrt()
+    };
+    samples
+        .iter()
+        .map(|(value, _)| {
+            let cost = samples
+                .iter()
+                .map(|(other, weight)| distance(value, other) * weight)
+                .sum::<f32>();
+            (value, cost)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(value, _)| *value)
+        .unwrap_or_default()
+}
+
+// 权重之和最大的颜色，相同时取先出现的
+fn mode(samples: &[Sample]) -> [f32; 4] {
+    let mut weights: HashMap<[u32; 4], (usize, f32)> = HashMap::new();
+    for (i, (value, weight)) in samples.iter().enumerate() {
+        weights.entry(value.map(f32::to_bits)).or_insert((i, 0.0)).1 += weight;
+    }
+    weights
+        .values()
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(i, _)| samples[*i].0)
+        .unwrap_or_default()
+}
+
+// 采样范围的统计，按形状加权，单位为 0~255
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SampleStats {
+    pub min: Color,
+    pub max: Color,
+    pub std_dev: [f32; 4],
+}
+
+impl SampleStats {
+    pub fn new(samples: &[(Color, f32)]) -> Self {
+        if samples.is_empty() {
+            return SampleStats::default();
+        }
+        let channels = |c: &Color| [c.r, c.g, c.b, c.a];
+        let mut min = [u8::MAX; 4];
+        let mut max = [u8::MIN; 4];
+        for (color, _) in samples {
+            for (i, value) in channels(color).into_iter().enumerate() {
+                min[i] = min[i].min(value);
+                max[i] = max[i].max(value);
+            }
+        }
+
+        let values: Vec<Sample> = samples
+            .iter()
+            .map(|(color, weight)| (channels(color).map(|v| v as f32), *weight))
+            .collect();
+        let mean = weighted_mean(&values);
+        let total = values.iter().map(|(_, w)| w).sum::<f32>();
+        let mut std_dev = [0.0; 4];
+        for (value, weight) in &values {
+            for i in 0..4 {
+                std_dev[i] += (value[i] - mean[i]).powi(2) * weight / total;
+            }
+        }
+
+        SampleStats {
+            min: Color::from(min),
+            max: Color::from(max),
+            std_dev: std_dev.map(f32::sqrt),
+        }
+    }
+}
+
+impl Display for SampleStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [r, g, b, a] = self.std_dev;
+        writeln!(f, "min {}", self.min)?;
+        writeln!(f, "max {}", self.max)?;
+        write!(f, "σ   r:{:.1} g:{:.1} b:{:.1}", r, g, b)?;
+        // 浮点误差会让不透明区域的 a 略大于 0，显示为 0.0 时省略
+        if a >= 0.05 {
+            write!(f, " a:{:.1}", a)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<SampleStats> for String {
+    fn from(value: SampleStats) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use crate::{
+        model::{
+            color::Color,
+            position::Position,
+            sample_aggregator::{Sample, SampleAggregator, SampleStats},
+            sample_shape::SampleShape,
+            sample_space::SampleSpace,
+        },
+        service::{
+            cursor_color::{SampleSettings, ScreenData, get_screen_data},
+            screen_source::mock::MockScreenSource,
+        },
+    };
+
+    // 保留一位小数，中位数的窗口和浮点累加有很小的误差
+    fn round(value: [f32; 4]) -> [f32; 4] {
+        value.map(|v| (v * 10.0).round() / 10.0)
+    }
+
+    fn gray(values: &[f32]) -> Vec<Sample> {
+        values.iter().map(|v| ([*v, *v, *v, 255.0], 1.0)).collect()
+    }
+
+    // 依次为 mean median vector-median mode trimmed-mean 的结果
+    fn each(values: &[f32]) -> Vec<f32> {
+        SampleAggregator::ALL
+            .iter()
+            .map(|aggregator| round(aggregator.aggregate(&gray(values)))[0])
+            .collect()
+    }
+
+    // 以 (2, 2) 为中心的 5×5 方形采样
+    fn sample(image: &RgbaImage, aggregator: SampleAggregator) -> ScreenData {
+        let settings = SampleSettings {
+            tex_size: 5,
+            sample_size: 5,
+            shape: SampleShape::Square,
+            aggregator,
+            space: SampleSpace::SRGB,
+        };
+        let source = MockScreenSource::new(image.clone());
+        get_screen_data(&source, Position { x: 2, y: 2 }, &settings).unwrap()
+    }
+
+    #[test]
+    fn each_method_on_plain_values() {
+        assert_eq!(
+            each(&[100.0, 100.0, 100.0, 100.0, 255.0]),
+            [131.0, 100.0, 100.0, 100.0, 100.0]
+        );
+        assert_eq!(
+            each(&[0.0, 10.0, 20.0, 60.0, 100.0]),
+            [38.0, 20.0, 20.0, 0.0, 30.0]
+        );
+        assert_eq!(
+            each(&[10.0, 10.0, 20.0, 30.0, 40.0]),
+            [22.0, 20.0, 20.0, 10.0, 20.0]
+        );
+        // 偶数个时中位数为中间两个的平均，向量中位数只取已有的值
+        assert_eq!(each(&[0.0, 10.0, 20.0, 30.0])[1..3], [15.0, 10.0]);
+        for aggregator in SampleAggregator::ALL {
+            assert_eq!(aggregator.aggregate(&[]), [0.0; 4]);
+            assert_eq!(
+                round(aggregator.aggregate(&gray(&[42.0]))),
+                [42.0, 42.0, 42.0, 255.0]
+            );
+        }
+    }
+
+    #[test]
+    fn weights_are_respected() {
+        let samples: Vec<Sample> = vec![([0.0; 4], 1.0), ([100.0; 4], 3.0)];
+        let aggregate = |aggregator: SampleAggregator| round(aggregator.aggregate(&samples));
+        assert_eq!(aggregate(SampleAggregator::Mean), [75.0; 4]);
+        assert_eq!(aggregate(SampleAggregator::Median), [100.0; 4]);
+        assert_eq!(aggregate(SampleAggregator::VectorMedian), [100.0; 4]);
+        assert_eq!(aggregate(SampleAggregator::Mode), [100.0; 4]);
+        // 累计权重 0~4 中保留 0.8~3.2，0 占 0.2，100 占 2.2
+        assert_eq!(aggregate(SampleAggregator::TrimmedMean), [91.7; 4]);
+
+        // 权重相同时众数取先出现的
+        let tie: Vec<Sample> = vec![([7.0; 4], 1.0), ([3.0; 4], 1.0)];
+        assert_eq!(SampleAggregator::Mode.aggregate(&tie), [7.0; 4]);
+    }
+
+    // 逐通道中位数可能拼出区域中没有的颜色，向量中位数总是区域中的某个像素
+    #[test]
+    fn vector_median_keeps_a_real_pixel() {
+        let samples: Vec<Sample> = vec![
+            ([255.0, 0.0, 0.0, 255.0], 1.0),
+            ([0.0, 255.0, 0.0, 255.0], 1.0),
+            ([0.0, 0.0, 255.0, 255.0], 1.0),
+            ([200.0, 200.0, 0.0, 255.0], 1.0),
+        ];
+        let median = round(SampleAggregator::Median.aggregate(&samples));
+        assert_eq!(median, [100.0, 100.0, 0.0, 255.0]);
+        assert!(!samples.iter().any(|(value, _)| *value == median));
+        let vector_median = SampleAggregator::VectorMedian.aggregate(&samples);
+        assert!(samples.iter().any(|(value, _)| *value == vector_median));
+    }
+
+    // 灰色区域中心有一个亮红色的像素，只有平均值受影响
+    #[test]
+    fn robust_methods_reject_a_single_outlier() {
+        let mut image = RgbaImage::from_pixel(5, 5, Rgba([100, 100, 100, 255]));
+        image.put_pixel(2, 2, Rgba([255, 0, 0, 255]));
+
+        let mean = sample(&image, SampleAggregator::Mean).cursor_pixel_color;
+        assert_eq!(mean, Color::new(106, 96, 96));
+        for aggregator in [
+            SampleAggregator::Median,
+            SampleAggregator::VectorMedian,
+            SampleAggregator::Mode,
+            SampleAggregator::TrimmedMean,
+        ] {
+            let color = sample(&image, aggregator).cursor_pixel_color;
+            assert_eq!(color, Color::new(100, 100, 100), "{:?}", aggregator);
+        }
+    }
+
+    // 左边两列红色，右边三列蓝色
+    #[test]
+    fn mixed_region() {
+        let image = RgbaImage::from_fn(5, 5, |x, _| {
+            if x < 2 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 255])
+            }
+        });
+        let color = |aggregator| sample(&image, aggregator).cursor_pixel_color;
+        let blue = Color::new(0, 0, 255);
+
+        assert_eq!(color(SampleAggregator::Mean), Color::new(102, 0, 153));
+        assert_eq!(color(SampleAggregator::Median), blue);
+        assert_eq!(color(SampleAggregator::VectorMedian), blue);
+        assert_eq!(color(SampleAggregator::Mode), blue);
+        // 每个通道去掉两端各 5 个像素，介于平均值和多数的颜色之间
+        assert_eq!(color(SampleAggregator::TrimmedMean), Color::new(85, 0, 170));
+
+        let stats = sample(&image, SampleAggregator::Mean).sample_stats;
+        assert_eq!(stats.min, Color::new(0, 0, 0));
+        assert_eq!(stats.max, Color::new(255, 0, 255));
+        // 两个值各占 0.4 和 0.6，标准差为 255 × √0.24
+        let spread = (255.0 * 0.24f32.sqrt() * 10.0).round() / 10.0;
+        assert_eq!(round(stats.std_dev), [spread, 0.0, spread, 0.0]);
+        assert_eq!(
+            stats.to_string(),
+            "min r:0 g:0 b:0\nmax r:255 g:0 b:255\nσ   r:124.9 g:0.0 b:124.9"
+        );
+    }
+
+    #[test]
+    fn stats_on_weighted_and_translucent_samples() {
+        assert_eq!(SampleStats::new(&[]), SampleStats::default());
+
+        let stats = SampleStats::new(&[
+            (Color::new_rgba(10, 20, 30, 0), 3.0),
+            (Color::new_rgba(50, 20, 30, 255), 1.0),
+        ]);
+        assert_eq!(stats.min, Color::new_rgba(10, 20, 30, 0));
+        assert_eq!(stats.max, Color::new_rgba(50, 20, 30, 255));
+        // 权重 3:1，标准差为差值 × √(0.75 × 0.25)
+        assert_eq!(round(stats.std_dev), [17.3, 0.0, 0.0, 110.4]);
+        assert!(stats.to_string().ends_with(" a:110.4"), "{}", stats);
+    }
+}
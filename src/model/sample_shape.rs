@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+// 范围取色的形状，决定采样范围内每个像素的权重
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleShape {
+    #[default]
+    Square,
+    Circle,
+    Gaussian,
+}
+
+impl SampleShape {
+    pub const ALL: [SampleShape; 3] = [
+        SampleShape::Square,
+        SampleShape::Circle,
+        SampleShape::Gaussian,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SampleShape::Square => "square",
+            SampleShape::Circle => "circle",
+            SampleShape::Gaussian => "gaussian",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            SampleShape::Square => SampleShape::Circle,
+            SampleShape::Circle => SampleShape::Gaussian,
+            SampleShape::Gaussian => SampleShape::Square,
+        }
+    }
+
+    // 采样范围边长为 2 * half + 1，圆的半径取到边长的一半
+    pub fn radius(half: i32) -> f32 {
+        half as f32 + 0.5
+    }
+
+    // 高斯核的标准差，2σ 与圆的半径相同
+    pub fn sigma(half: i32) -> f32 {
+        Self::radius(half) / 2.0
+    }
+
+    // 相对中心 (dx, dy) 的像素权重，为 0 时不参与采样
+    pub fn weight(&self, dx: i32, dy: i32, half: i32) -> f32 {
+        let distance_sq = (dx * dx + dy * dy) as f32;
+        match self {
+            SampleShape::Square => 1.0,
+            SampleShape::Circle => {
+                if distance_sq <= Self::radius(half).powi(2) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            SampleShape::Gaussian => {
+                let sigma = Self::sigma(half);
+                (-distance_sq / (2.0 * sigma * sigma)).exp()
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::color::{
+    Color,
+    cie::{alpha_to_u8, linear_to_srgb, srgb_to_linear},
+    oklab::OKLab,
+};
+
+// 合成采样像素时使用的颜色空间
+// 直接平均 sRGB 编码值会让明暗混合偏暗，线性光下平均符合物理上的混合，OKLab 下平均更接近感知
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SampleSpace {
+    #[default]
+    SRGB,
+    Linear,
+    OKLab,
+}
+
+impl SampleSpace {
+    pub const ALL: [SampleSpace; 3] = [SampleSpace::SRGB, SampleSpace::Linear, SampleSpace::OKLab];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SampleSpace::SRGB => "sRGB",
+            SampleSpace::Linear => "linear light",
+            SampleSpace::OKLab => "OKLab",
+        }
+    }
+
+    // 转成四个通道，不透明度的单位与其余通道一致
+    pub fn encode(&self, color: &Color) -> [f32; 4] {
+        match self {
+            SampleSpace::SRGB => [color.r, color.g, color.b, color.a].map(|v| v as f32),
+            SampleSpace::Linear => [
+                srgb_to_linear(color.r),
+                srgb_to_linear(color.g),
+                srgb_to_linear(color.b),
+                color.alpha(),
+            ],
+            SampleSpace::OKLab => {
+                let oklab = color.to_oklab();
+                [oklab.l, oklab.a, oklab.b, oklab.alpha]
+            }
+        }
+    }
+
+    pub fn decode(&self, value: [f32; 4]) -> Color {
+        match self {
+            SampleSpace::SRGB => {
+                // 四舍五入，避免均匀区域的值被截断小 1
+                let [r, g, b, a] = value.map(|v| v.round().clamp(0.0, 255.0) as u8);
+                Color { r, g, b, a }
+            }
+            SampleSpace::Linear => Color {
+                r: linear_to_srgb(value[0]),
+                g: linear_to_srgb(value[1]),
+                b: linear_to_srgb(value[2]),
+                a: alpha_to_u8(value[3]),
+            },
+            SampleSpace::OKLab => {
+                let [l, a, b, alpha] = value;
+                OKLab { l, a, b, alpha }.to_rgb()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use crate::{
+        model::{
+            color::Color, position::Position, sample_aggregator::SampleAggregator,
+            sample_shape::SampleShape, sample_space::SampleSpace,
+        },
+        service::{
+            cursor_color::{SampleSettings, get_screen_data},
+            screen_source::mock::MockScreenSource,
+        },
+    };
+
+    // 黑白棋盘格上以白色像素为中心做 5×5 平均，13 白 12 黑
+    fn checkerboard_mean(space: SampleSpace) -> Color {
+        let image = RgbaImage::from_fn(9, 9, |x, y| {
+            if (x + y) % 2 == 0 {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        });
+        let settings = SampleSettings {
+            tex_size: 5,
+            sample_size: 5,
+            shape: SampleShape::Square,
+            aggregator: SampleAggregator::Mean,
+            space,
+        };
+        let source = MockScreenSource::new(image);
+        get_screen_data(&source, Position { x: 4, y: 4 }, &settings)
+            .unwrap()
+            .cursor_pixel_color
+    }
+
+    fn assert_gray(color: Color, expected: u8) {
+        assert_eq!(color.a, 255);
+        for value in [color.r, color.g, color.b] {
+            assert!(value.abs_diff(expected) <= 1, "{:?} != {}", color, expected);
+        }
+    }
+
+    #[test]
+    fn checkerboard_means_differ_between_spaces() {
+        // sRGB 编码值平均 255 × 0.52
+        let srgb = checkerboard_mean(SampleSpace::SRGB);
+        assert_gray(srgb, 133);
+        // 线性光 0.52 转回 sRGB 更亮
+        let linear = checkerboard_mean(SampleSpace::Linear);
+        assert_gray(linear, 191);
+        // OKLab 的 L 为 0.52，对应线性光 0.52³，更暗
+        let oklab = checkerboard_mean(SampleSpace::OKLab);
+        assert_gray(oklab, 105);
+        assert!(oklab.r < srgb.r && srgb.r < linear.r);
+    }
+
+    #[test]
+    fn uniform_areas_are_unchanged_in_every_space() {
+        let color = Color::new(12, 200, 99);
+        let [r, g, b, a] = [color.r, color.g, color.b, color.a];
+        let source = MockScreenSource::new(RgbaImage::from_pixel(9, 9, Rgba([r, g, b, a])));
+        for space in SampleSpace::ALL {
+            let settings = SampleSettings {
+                tex_size: 5,
+                sample_size: 5,
+                shape: SampleShape::Square,
+                aggregator: SampleAggregator::Mean,
+                space,
+            };
+            let data = get_screen_data(&source, Position { x: 4, y: 4 }, &settings).unwrap();
+            assert_eq!(data.cursor_pixel_color, color, "{:?}", space);
+        }
+    }
+}
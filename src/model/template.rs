@@ -0,0 +1,520 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::model::color::{Color, cie::WhitePoint};
+
+// 输出格式模板，如 "rgb({r}, {g}, {b})"、"{hex:lower}"、"0x{a:X}{r:X}{g:X}{b:X}"
+// 占位符为 {变量} 或 {变量:格式}，{{ 和 }} 输出字面的花括号
+//
+// 变量：
+//   r g b a                     0~255 的整数
+//   hex hex8                    #RRGGBB、#RRGGBBAA
+//   h s l                       HSL，s、l 为 0~100
+//   hsv.h hsv.s v               HSV，s、v 为 0~100
+//   x y z                       XYZ (D65)，Y 为 0~100
+//   lab.l lab.a lab.b           L*a*b* (D65)，lab50.* 为 D50
+//   lch.l lch.c lch.h           LCh (D65)，lch50.* 为 D50
+//   oklab.l oklab.a oklab.b     OKLab，l 为 0~1
+//   oklch.l oklch.c oklch.h     OKLCh，l 为 0~1
+// 格式：
+//   .N      保留 N 位小数
+//   f f.N   0~1 的小数，用于 r g b a 和 0~100 的分量，默认 3 位
+//   % %.N   r g b a 的百分比
+//   x X     r g b a 的两位十六进制
+//   lower upper   hex、hex8 的大小写
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    source: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Field(Variable, Spec),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Variable {
+    Channel(usize), // r g b a
+    Hex { alpha: bool },
+    Number(NumberVariable),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumberVariable {
+    HslH,
+    HslS,
+    HslL,
+    HsvH,
+    HsvS,
+    HsvV,
+    X,
+    Y,
+    Z,
+    Lab(WhitePoint, usize),
+    LCh(WhitePoint, usize),
+    OKLab(usize),
+    OKLCh(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spec {
+    Default,
+    Precision(usize),
+    Fraction(usize),
+    Percent(usize),
+    Hex { upper: bool },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    UnknownVariable(String),
+    InvalidSpec { variable: String, spec: String },
+    UnclosedBrace(usize),
+    UnmatchedBrace(usize),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::UnknownVariable(name) => write!(f, "unknown variable: {{{}}}", name),
+            TemplateError::InvalidSpec { variable, spec } => {
+                write!(f, "invalid format \"{}\" for {{{}}}", spec, variable)
+            }
+            TemplateError::UnclosedBrace(pos) => write!(f, "unclosed '{{' at {}", pos),
+            TemplateError::UnmatchedBrace(pos) => write!(f, "unmatched '}}' at {}", pos),
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+impl Variable {
+    fn parse(name: &str) -> Option<Self> {
+        use NumberVariable::*;
+        use WhitePoint::*;
+
+        let variable = match name {
+            "r" => Variable::Channel(0),
+            "g" => Variable::Channel(1),
+            "b" => Variable::Channel(2),
+            "a" => Variable::Channel(3),
+            "hex" => Variable::Hex { alpha: false },
+            "hex8" => Variable::Hex { alpha: true },
+            _ => Variable::Number(match name {
+                "h" | "hsl.h" => HslH,
+                "s" | "hsl.s" => HslS,
+                "l" | "hsl.l" => HslL,
+                "hsv.h" => HsvH,
+                "hsv.s" => HsvS,
+                "v" | "hsv.v" => HsvV,
+                "x" => X,
+                "y" => Y,
+                "z" => Z,
+                "lab.l" => Lab(D65, 0),
+                "lab.a" => Lab(D65, 1),
+                "lab.b" => Lab(D65, 2),
+                "lab50.l" => Lab(D50, 0),
+                "lab50.a" => Lab(D50, 1),
+                "lab50.b" => Lab(D50, 2),
+                "lch.l" => LCh(D65, 0),
+                "lch.c" => LCh(D65, 1),
+                "lch.h" => LCh(D65, 2),
+                "lch50.l" => LCh(D50, 0),
+                "lch50.c" => LCh(D50, 1),
+                "lch50.h" => LCh(D50, 2),
+                "oklab.l" => OKLab(0),
+                "oklab.a" => OKLab(1),
+                "oklab.b" => OKLab(2),
+                "oklch.l" => OKLCh(0),
+                "oklch.c" => OKLCh(1),
+                "oklch.h" => OKLCh(2),
+                _ => return None,
+            }),
+        };
+        Some(variable)
+    }
+}
+
+impl NumberVariable {
+    fn value(&self, color: &Color) -> f32 {
+        match self {
+            NumberVariable::HslH => color.to_hsl().h,
+            NumberVariable::HslS => color.to_hsl().s * 100.0,
+            NumberVariable::HslL => color.to_hsl().l * 100.0,
+            NumberVariable::HsvH => color.to_hsv().h,
+            NumberVariable::HsvS => color.to_hsv().s * 100.0,
+            NumberVariable::HsvV => color.to_hsv().v * 100.0,
+            NumberVariable::X => color.to_xyz().x * 100.0,
+            NumberVariable::Y => color.to_xyz().y * 100.0,
+            NumberVariable::Z => color.to_xyz().z * 100.0,
+            NumberVariable::Lab(white, i) => {
+                let lab = color.to_lab(*white);
+                [lab.l, lab.a, lab.b][*i]
+            }
+            NumberVariable::LCh(white, i) => {
+                let lch = color.to_lch(*white);
+                [lch.l, lch.c, lch.h][*i]
+            }
+            NumberVariable::OKLab(i) => {
+                let oklab = color.to_oklab();
+                [oklab.l, oklab.a, oklab.b][*i]
+            }
+            NumberVariable::OKLCh(i) => {
+                let oklch = color.to_oklch();
+                [oklch.l, oklch.c, oklch.h][*i]
+            }
+        }
+    }
+
+    fn default_precision(&self) -> usize {
+        match self {
+            NumberVariable::OKLab(_) | NumberVariable::OKLCh(0 | 1) => 3,
+            NumberVariable::OKLCh(_) => 2,
+            _ => 1,
+        }
+    }
+
+    // 0~100 的分量可以用 f 转成 0~1
+    fn is_percentage(&self) -> bool {
+        matches!(
+            self,
+            NumberVariable::HslS
+                | NumberVariable::HslL
+                | NumberVariable::HsvS
+                | NumberVariable::HsvV
+        )
+    }
+}
+
+fn parse_spec(variable: &Variable, name: &str, spec: &str) -> Result<Spec, TemplateError> {
+    let invalid = || TemplateError::InvalidSpec {
+        variable: name.to_string(),
+        spec: spec.to_string(),
+    };
+    let precision = |digits: &str, default: usize| -> Result<usize, TemplateError> {
+        match digits {
+            "" => Ok(default),
+            _ => digits
+                .strip_prefix('.')
+                .and_then(|n| n.parse().ok())
+                .filter(|n| *n <= 10)
+                .ok_or_else(invalid),
+        }
+    };
+
+    let channel = matches!(variable, Variable::Channel(_));
+    let fraction = channel || matches!(variable, Variable::Number(n) if n.is_percentage());
+    match (variable, spec) {
+        (_, "") => Ok(Spec::Default),
+        (Variable::Hex { .. }, "lower") => Ok(Spec::Hex { upper: false }),
+        (Variable::Hex { .. }, "upper") => Ok(Spec::Hex { upper: true }),
+        (Variable::Channel(_), "x") => Ok(Spec::Hex { upper: false }),
+        (Variable::Channel(_), "X") => Ok(Spec::Hex { upper: true }),
+        (Variable::Hex { .. }, _) => Err(invalid()),
+        _ if spec.starts_with('.') => Ok(Spec::Precision(precision(spec, 0)?)),
+        _ if fraction && spec.starts_with('f') => Ok(Spec::Fraction(precision(&spec[1..], 3)?)),
+        _ if channel && spec.starts_with('%') => Ok(Spec::Percent(precision(&spec[1..], 0)?)),
+        _ => Err(invalid()),
+    }
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        let mut parts = vec![];
+        let mut text = String::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((pos, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err(TemplateError::UnmatchedBrace(pos)),
+                '{' => {
+                    let end = source[pos..]
+                        .find('}')
+                        .map(|i| pos + i)
+                        .ok_or(TemplateError::UnclosedBrace(pos))?;
+                    let field = &source[pos + 1..end];
+                    let (name, spec) = field.split_once(':').unwrap_or((field, ""));
+                    let (name, spec) = (name.trim(), spec.trim());
+                    let variable = Variable::parse(name)
+                        .ok_or_else(|| TemplateError::UnknownVariable(name.to_string()))?;
+                    let spec = parse_spec(&variable, name, spec)?;
+
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Field(variable, spec));
+                    while chars.peek().is_some_and(|(i, _)| *i <= end) {
+                        chars.next();
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        Ok(Template {
+            source: source.to_string(),
+            parts,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn render(&self, color: &Color) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::Field(variable, spec) => out.push_str(&render_field(variable, spec, color)),
+            }
+        }
+        out
+    }
+}
+
+fn render_field(variable: &Variable, spec: &Spec, color: &Color) -> String {
+    match variable {
+        Variable::Channel(i) => {
+            let value = [color.r, color.g, color.b, color.a][*i];
+            match spec {
+                Spec::Default => value.to_string(),
+                Spec::Precision(p) => format!("{:.*}", p, value as f32),
+                Spec::Fraction(p) => format!("{:.*}", p, value as f32 / 255.0),
+                Spec::Percent(p) => format!("{:.*}", p, value as f32 / 255.0 * 100.0),
+                Spec::Hex { upper: true } => format!("{:02X}", value),
+                Spec::Hex { upper: false } => format!("{:02x}", value),
+            }
+        }
+        Variable::Hex { alpha } => {
+            let hex = if *alpha {
+                color.to_hex_alpha()
+            } else {
+                color.to_hex()
+            };
+            match spec {
+                Spec::Hex { upper: false } => hex.to_lowercase(),
+                _ => hex,
+            }
+        }
+        Variable::Number(number) => {
+            let value = number.value(color);
+            // 加 0.0 避免输出 -0.0
+            match spec {
+                Spec::Precision(p) => format!("{:.*}", p, value + 0.0),
+                Spec::Fraction(p) => format!("{:.*}", p, value / 100.0 + 0.0),
+                _ => format!("{:.*}", number.default_precision(), value + 0.0),
+            }
+        }
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Template::parse(s)
+    }
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+// 有名字的模板，读数行从中选择
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplatePreset {
+    pub name: String,
+    pub template: Template,
+}
+
+impl TemplatePreset {
+    pub fn new(name: impl Into<String>, source: &str) -> Result<Self, TemplateError> {
+        Ok(TemplatePreset {
+            name: name.into(),
+            template: Template::parse(source)?,
+        })
+    }
+
+    pub fn builtin() -> Vec<TemplatePreset> {
+        [
+            ("rgb()", "rgb({r}, {g}, {b})"),
+            ("rgba()", "rgba({r}, {g}, {b}, {a:f.2})"),
+            ("rgb float", "{r:f}, {g:f}, {b:f}"),
+            ("hex lower", "{hex:lower}"),
+            ("hex8", "{hex8}"),
+            ("0xAARRGGBB", "0x{a:X}{r:X}{g:X}{b:X}"),
+            ("hsl()", "hsl({h:.0}, {s:.0}%, {l:.0}%)"),
+            ("hsv()", "hsv({hsv.h:.0}, {hsv.s:.0}%, {v:.0}%)"),
+            ("lab()", "lab({lab50.l:.2} {lab50.a:.2} {lab50.b:.2})"),
+            ("oklch()", "oklch({oklch.l:.3} {oklch.c:.3} {oklch.h:.2})"),
+            (
+                "UIColor",
+                "UIColor(red: {r:f}, green: {g:f}, blue: {b:f}, alpha: {a:f})",
+            ),
+        ]
+        .iter()
+        .filter_map(|(name, source)| TemplatePreset::new(*name, source).ok())
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        color::Color,
+        template::{Template, TemplateError, TemplatePreset},
+    };
+
+    fn render(source: &str, color: Color) -> String {
+        Template::parse(source)
+            .unwrap_or_else(|e| panic!("{}: {}", source, e))
+            .render(&color)
+    }
+
+    #[test]
+    fn placeholders_and_text() {
+        let color = Color::new_rgba(255, 128, 0, 64);
+        assert_eq!(render("rgb({r}, {g}, {b})", color), "rgb(255, 128, 0)");
+        assert_eq!(render("{ r }/{a}", color), "255/64");
+        assert_eq!(render("no fields", color), "no fields");
+        assert_eq!(render("", color), "");
+        assert_eq!(render("{hex} {hex8}", color), "#FF8000 #FF800040");
+        assert_eq!(
+            render("{hex:lower} {hex8:upper}", color),
+            "#ff8000 #FF800040"
+        );
+        assert_eq!(render("色 {r}", color), "色 255");
+    }
+
+    #[test]
+    fn escaped_braces() {
+        let color = Color::new(1, 2, 3);
+        assert_eq!(render("{{r}}", color), "{r}");
+        assert_eq!(render("{{{r}}}", color), "{1}");
+        assert_eq!(render("}}{{", color), "}{");
+        assert_eq!(
+            render(".c {{ color: {hex}; }}", color),
+            ".c { color: #010203; }"
+        );
+    }
+
+    #[test]
+    fn channel_specs() {
+        let color = Color::new_rgba(255, 128, 0, 64);
+        assert_eq!(render("{r:%}", color), "100");
+        assert_eq!(render("{g:%}", color), "50");
+        assert_eq!(render("{g:%.2}", color), "50.20");
+        assert_eq!(render("{g:f}", color), "0.502");
+        assert_eq!(render("{a:f.2}", color), "0.25");
+        assert_eq!(render("{g:.1}", color), "128.0");
+        assert_eq!(render("0x{a:X}{r:X}{g:X}{b:X}", color), "0x40FF8000");
+        assert_eq!(render("{r:x}{b:x}", color), "ff00");
+    }
+
+    #[test]
+    fn number_specs() {
+        let red = Color::new(255, 0, 0);
+        assert_eq!(render("{h} {s} {l}", red), "0.0 100.0 50.0");
+        assert_eq!(render("{s:f} {l:f.1} {v:f.0}", red), "1.000 0.5 1");
+        assert_eq!(render("{hsv.h:.0} {hsv.s:.0} {v:.0}", red), "0 100 100");
+        assert_eq!(
+            render("{lab.l:.2} {lab.a:.2} {lab.b:.2}", red),
+            "53.24 80.09 67.20"
+        );
+        assert_eq!(render("{lab50.l:.0} {lch50.c:.0}", red), "54 107");
+        assert_eq!(render("{lch.h:.0}", red), "40");
+        assert_eq!(render("{y}", red), "21.3");
+        assert_eq!(
+            render("{oklch.l} {oklch.c} {oklch.h}", red),
+            "0.628 0.258 29.23"
+        );
+        assert_eq!(render("{oklab.a}", red), "0.225");
+        // 灰色不输出 -0.0
+        let gray = Color::new(128, 128, 128);
+        assert_eq!(
+            render("{lab.a} {lab.b} {oklab.a:.3}", gray),
+            "0.0 0.0 0.000"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let parse = |source: &str| Template::parse(source).unwrap_err();
+        let invalid = |variable: &str, spec: &str| TemplateError::InvalidSpec {
+            variable: variable.to_string(),
+            spec: spec.to_string(),
+        };
+
+        assert_eq!(
+            parse("{q}"),
+            TemplateError::UnknownVariable("q".to_string())
+        );
+        assert_eq!(parse("{}"), TemplateError::UnknownVariable("".to_string()));
+        assert_eq!(
+            parse("{r{g}"),
+            TemplateError::UnknownVariable("r{g".to_string())
+        );
+        assert_eq!(parse("ab{r"), TemplateError::UnclosedBrace(2));
+        assert_eq!(parse("{r}}"), TemplateError::UnmatchedBrace(3));
+        assert_eq!(parse("a}b"), TemplateError::UnmatchedBrace(1));
+
+        assert_eq!(parse("{hex:x}"), invalid("hex", "x"));
+        assert_eq!(parse("{hex:.2}"), invalid("hex", ".2"));
+        assert_eq!(parse("{h:f}"), invalid("h", "f"));
+        assert_eq!(parse("{s:%}"), invalid("s", "%"));
+        assert_eq!(parse("{lab.l:X}"), invalid("lab.l", "X"));
+        assert_eq!(parse("{r:.11}"), invalid("r", ".11"));
+        assert_eq!(parse("{r:f2}"), invalid("r", "f2"));
+        assert_eq!(parse("{r:%.x}"), invalid("r", "%.x"));
+
+        assert_eq!(parse("{q}").to_string(), "unknown variable: {q}");
+        assert_eq!(
+            parse("{hex:x}").to_string(),
+            "invalid format \"x\" for {hex}"
+        );
+        assert_eq!(parse("ab{r").to_string(), "unclosed '{' at 2");
+        assert_eq!(parse("a}b").to_string(), "unmatched '}' at 1");
+    }
+
+    #[test]
+    fn source_and_presets() {
+        let template: Template = "{r}-{g}".parse().unwrap();
+        assert_eq!(template.source(), "{r}-{g}");
+        assert_eq!(template.to_string(), "{r}-{g}");
+
+        // 内置模板都能解析
+        let presets = TemplatePreset::builtin();
+        assert_eq!(presets.len(), 11);
+        let find = |name: &str| {
+            presets
+                .iter()
+                .find(|preset| preset.name == name)
+                .unwrap()
+                .template
+                .render(&Color::new_rgba(255, 0, 0, 128))
+        };
+        assert_eq!(find("rgba()"), "rgba(255, 0, 0, 0.50)");
+        assert_eq!(find("0xAARRGGBB"), "0x80FF0000");
+        assert_eq!(find("hsl()"), "hsl(0, 100%, 50%)");
+        assert_eq!(
+            find("UIColor"),
+            "UIColor(red: 1.000, green: 0.000, blue: 0.000, alpha: 0.502)"
+        );
+        assert!(TemplatePreset::new("bad", "{nope}").is_err());
+    }
+}
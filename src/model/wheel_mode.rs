@@ -4,8 +4,20 @@ pub enum WheelMode {
     HSV,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for WheelMode {
     fn default() -> Self {
         WheelMode::HSV
     }
-}
\ No newline at end of file
+}
+
+impl WheelMode {
+    pub const ALL: [WheelMode; 2] = [WheelMode::HSL, WheelMode::HSV];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WheelMode::HSL => "HSL",
+            WheelMode::HSV => "HSV",
+        }
+    }
+}
@@ -1,9 +1,15 @@
 use std::{error::Error, fmt::Display};
 
-use screenshots::Screen;
-use windows::Win32::{Foundation::POINT, UI::WindowsAndMessaging::GetCursorPos};
-
-use crate::model::{color::Color, position::Position};
+use crate::{
+    model::{
+        color::Color,
+        position::Position,
+        sample_aggregator::{Sample, SampleAggregator, SampleStats},
+        sample_shape::SampleShape,
+        sample_space::SampleSpace,
+    },
+    service::screen_source::ScreenSource,
+};
 
 #[derive(Debug)]
 pub enum GetCursorColorError {
@@ -11,6 +17,7 @@ pub enum GetCursorColorError {
     UnableGetScreens,
     UnableGetBuffer,
     ScreenCountIsZero,
+    UnablePickColor,
 }
 
 impl Display for GetCursorColorError {
@@ -22,102 +29,162 @@ impl Display for GetCursorColorError {
             GetCursorColorError::UnableGetScreens => write!(f, "unable to get screens"),
             GetCursorColorError::UnableGetBuffer => write!(f, "unable to get screen buffer"),
             GetCursorColorError::ScreenCountIsZero => write!(f, "screen count is zero"),
+            GetCursorColorError::UnablePickColor => write!(f, "unable to pick color"),
         }
     }
 }
 
 impl Error for GetCursorColorError {}
 
-pub fn get_mouse_position() -> Result<Position, GetCursorColorError> {
-    unsafe {
-        let mut point = POINT::default();
-        match GetCursorPos(&mut point) {
-            Ok(_) => {
-                // println!("Mouse position: ({}, {})", point.x, point.y);
-                Ok(Position {
-                    x: point.x,
-                    y: point.y,
-                })
-            }
-            Err(_) => Err(GetCursorColorError::UnableGetMousePosition),
+pub struct ScreenData {
+    pub cursor_pixel_color: Color,
+    pub screen_pixel_colors: Vec<Color>,
+    pub screen_bounds: GridBounds,
+    pub sample_stats: SampleStats,
+}
+
+// 像素网格中位于显示器内的矩形，max 不含，其余像素是填充的透明黑色
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GridBounds {
+    pub min: (i32, i32),
+    pub max: (i32, i32),
+}
+
+impl GridBounds {
+    pub fn full(width: usize, height: usize) -> Self {
+        GridBounds {
+            min: (0, 0),
+            max: (width as i32, height as i32),
         }
     }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        (self.min.0..self.max.0).contains(&x) && (self.min.1..self.max.1).contains(&y)
+    }
 }
 
-pub struct ScreenData {
-    pub cursor_pixel_color: Color,
-    pub screen_pixel_colors: Vec<Color>,
+// 一次采样使用的设置，tex_size 为画面的边长
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleSettings {
+    pub tex_size: usize,
+    pub sample_size: usize,
+    pub shape: SampleShape,
+    pub aggregator: SampleAggregator,
+    pub space: SampleSpace,
+}
+
+// 读取鼠标位置并在该处采样，窗口的后台线程和 stream 命令共用
+pub fn sample_cursor(
+    source: &(impl ScreenSource + ?Sized),
+    settings: &SampleSettings,
+) -> Result<(Position, ScreenData), GetCursorColorError> {
+    let position = source.cursor_position()?;
+    let data = get_screen_data(source, position, settings)?;
+    Ok((position, data))
 }
+
 pub fn get_screen_data(
+    source: &(impl ScreenSource + ?Sized),
     position: Position,
-    screen_tex_size: usize,
-    screen_sample_size: usize,
+    settings: &SampleSettings,
 ) -> Result<ScreenData, GetCursorColorError> {
-    let screens = Screen::all().or(Err(GetCursorColorError::UnableGetScreens))?;
-    for screen in screens {
-        let scale = screen.display_info.scale_factor;
+    let monitors = source.monitors()?;
+    for monitor in monitors {
+        let scale = monitor.scale_factor;
         let physical_x = position.x;
         let physical_y = position.y;
 
-        let physical_screen_x = (screen.display_info.x as f32 * scale).round() as i32;
-        let physical_screen_y = (screen.display_info.y as f32 * scale).round() as i32;
-        let physical_screen_width = (screen.display_info.width as f32 * scale).round() as i32;
-        let physical_screen_height = (screen.display_info.height as f32 * scale).round() as i32;
+        let physical_screen_x = (monitor.x as f32 * scale).round() as i32;
+        let physical_screen_y = (monitor.y as f32 * scale).round() as i32;
+        let physical_screen_width = (monitor.width as f32 * scale).round() as i32;
+        let physical_screen_height = (monitor.height as f32 * scale).round() as i32;
 
         if physical_screen_x <= physical_x
             && physical_x < physical_screen_x + physical_screen_width
             && physical_screen_y <= physical_y
             && physical_y < physical_screen_y + physical_screen_height
         {
-            let half_size = (screen_tex_size as f32 / 2.0).floor() as u32;
-            let half_sample_size = (screen_sample_size as f32 / 2.0).floor() as i32;
-            let image = screen
-                .capture_area(
-                    physical_x - physical_screen_x - half_size as i32,
-                    physical_y - physical_screen_y - half_size as i32,
-                    half_size * 2 + 1,
-                    half_size * 2 + 1,
-                )
-                .or(Err(GetCursorColorError::UnableGetBuffer))?;
-
-            let mut sample_colors = vec![];
-            let half_sample_size = half_sample_size.min(half_size as i32);
-            for dx in -half_sample_size..=half_sample_size {
-                for dy in -half_sample_size..=half_sample_size {
-                    let x = (half_size as i32 + dx) as u32;
-                    let y = (half_size as i32 + dy) as u32;
-
-                    let pixel = image.get_pixel(x, y);
-                    sample_colors.push(Color {
-                        r: pixel.0[0],
-                        g: pixel.0[1],
-                        b: pixel.0[2],
-                    });
-                }
-            }
-            let len = sample_colors.len() as f32;
-            let color = Color {
-                r: (sample_colors.iter().map(|c| c.r as u32).sum::<u32>() as f32 / len) as u8,
-                g: (sample_colors.iter().map(|c| c.g as u32).sum::<u32>() as f32 / len) as u8,
-                b: (sample_colors.iter().map(|c| c.b as u32).sum::<u32>() as f32 / len) as u8,
-            };
+            let half_size = (settings.tex_size as f32 / 2.0).floor() as u32;
+            let left = physical_x - physical_screen_x - half_size as i32;
+            let top = physical_y - physical_screen_y - half_size as i32;
+            let image =
+                source.capture_area(&monitor, left, top, half_size * 2 + 1, half_size * 2 + 1)?;
 
             let (width, height) = image.dimensions();
+            let bounds = GridBounds {
+                min: ((-left).max(0), (-top).max(0)),
+                max: (
+                    (physical_screen_width - left).min(width as i32),
+                    (physical_screen_height - top).min(height as i32),
+                ),
+            };
             let mut colors = vec![];
             for y in 0..height {
                 for x in 0..width {
                     let pixel = image.get_pixel(x, y);
-                    let color = Color::new(pixel[0], pixel[1], pixel[2]);
+                    let color = Color::new_rgba(pixel[0], pixel[1], pixel[2], pixel[3]);
                     colors.push(color);
                 }
             }
+            let (color, sample_stats) = sample_region(
+                &colors,
+                width as usize,
+                bounds,
+                (half_size as i32, half_size as i32),
+                settings,
+            );
 
             return Ok(ScreenData {
                 cursor_pixel_color: color,
                 screen_pixel_colors: colors,
+                screen_bounds: bounds,
+                sample_stats,
             });
         }
     }
 
     Err(GetCursorColorError::ScreenCountIsZero)
 }
+
+// 在 width 宽的像素网格中，以 center 为中心合成一个颜色，
+// 只有 bounds 内（显示器内）的像素参与，采样范围不超过 settings.tex_size 的画面
+pub fn sample_region(
+    colors: &[Color],
+    width: usize,
+    bounds: GridBounds,
+    center: (i32, i32),
+    settings: &SampleSettings,
+) -> (Color, SampleStats) {
+    let height = colors.len().checked_div(width).unwrap_or(0);
+    let bounds = GridBounds {
+        min: (bounds.min.0.max(0), bounds.min.1.max(0)),
+        max: (
+            bounds.max.0.min(width as i32),
+            bounds.max.1.min(height as i32),
+        ),
+    };
+    let half_sample_size = (settings.sample_size / 2).min(settings.tex_size / 2) as i32;
+
+    // 按形状给每个像素加权，权重为 0 的像素不参与
+    let mut sample_colors = vec![];
+    for dx in -half_sample_size..=half_sample_size {
+        for dy in -half_sample_size..=half_sample_size {
+            let weight = settings.shape.weight(dx, dy, half_sample_size);
+            let (x, y) = (center.0 + dx, center.1 + dy);
+            if weight <= 0.0 || !bounds.contains(x, y) {
+                continue;
+            }
+            sample_colors.push((colors[y as usize * width + x as usize], weight));
+        }
+    }
+
+    // 在选定的颜色空间中合成，再转回 sRGB
+    let samples: Vec<Sample> = sample_colors
+        .iter()
+        .map(|(c, w)| (settings.space.encode(c), *w))
+        .collect();
+    let color = settings
+        .space
+        .decode(settings.aggregator.aggregate(&samples));
+    (color, SampleStats::new(&sample_colors))
+}
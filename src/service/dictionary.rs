@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use crate::model::named_color::{ColorDictionary, DictionaryError};
+use crate::service::store;
+
+// 用户词典放在 <配置目录>/colorose/dictionaries 下，启动时自动加载
+const DICTIONARY_DIR: &str = "dictionaries";
+
+// 按扩展名读取 .json 或 .csv 词典，词典名为文件名
+pub fn load_dictionary(path: impl AsRef<Path>) -> Result<ColorDictionary, DictionaryError> {
+    let path = path.as_ref();
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let parse: fn(String, &str) -> Result<ColorDictionary, DictionaryError> =
+        match extension.as_str() {
+            "json" => ColorDictionary::from_json,
+            "csv" => ColorDictionary::from_csv,
+            _ => return Err(DictionaryError::UnsupportedFormat(extension)),
+        };
+    let text = std::fs::read_to_string(path).map_err(DictionaryError::Io)?;
+    parse(name, &text)
+}
+
+// 加载失败的文件直接跳过
+pub fn load_user_dictionaries() -> Vec<ColorDictionary> {
+    store::config_files(DICTIONARY_DIR)
+        .iter()
+        .filter_map(|path| load_dictionary(path).ok())
+        .collect()
+}
@@ -0,0 +1,17 @@
+use crate::model::history::{History, HistoryError};
+use crate::service::store;
+
+// 取色历史保存在 <配置目录>/colorose/history.json
+const HISTORY_FILE: &str = "history.json";
+
+// 文件不存在时返回空的历史
+pub fn load_history() -> Result<History, HistoryError> {
+    match store::read_config(HISTORY_FILE).map_err(HistoryError::Io)? {
+        Some(text) => History::from_json(&text),
+        None => Ok(History::new()),
+    }
+}
+
+pub fn save_history(history: &History) -> Result<(), HistoryError> {
+    store::write_config(HISTORY_FILE, &history.to_json()?).map_err(HistoryError::Io)
+}
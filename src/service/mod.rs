@@ -1,2 +1,10 @@
 pub mod cursor_color;
-pub mod utils;
\ No newline at end of file
+pub mod dictionary;
+pub mod fonts;
+pub mod history;
+pub mod palette;
+pub mod platform;
+pub mod screen_source;
+pub mod store;
+pub mod template;
+pub mod utils;
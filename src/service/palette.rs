@@ -0,0 +1,122 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::model::palette::{Palette, PaletteCollection, PaletteError, PaletteFormat};
+use crate::service::store;
+
+// 色板保存在 <配置目录>/colorose/palettes.json
+const PALETTE_FILE: &str = "palettes.json";
+
+// 文件不存在时返回空的色板集合
+pub fn load_palettes() -> Result<PaletteCollection, PaletteError> {
+    match store::read_config(PALETTE_FILE).map_err(PaletteError::Io)? {
+        Some(text) => PaletteCollection::from_json(&text),
+        None => Ok(PaletteCollection::new()),
+    }
+}
+
+pub fn save_palettes(palettes: &PaletteCollection) -> Result<(), PaletteError> {
+    store::write_config(PALETTE_FILE, &palettes.to_json()?).map_err(PaletteError::Io)
+}
+
+// 按扩展名读取其他软件的色板文件，文件中没有色板名时用文件名
+pub fn import_palette(path: impl AsRef<Path>) -> Result<Palette, PaletteError> {
+    let path = path.as_ref();
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let format = PaletteFormat::from_extension(&extension)
+        .ok_or(PaletteError::UnsupportedFormat(extension))?;
+    let bytes = std::fs::read(path).map_err(PaletteError::Io)?;
+    format.read(name, &bytes)
+}
+
+// 导出到下载目录，文件名为色板名，返回写入的路径
+pub fn export_palette(palette: &Palette, format: PaletteFormat) -> Result<PathBuf, PaletteError> {
+    let dir = dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| PaletteError::Io(std::io::ErrorKind::NotFound.into()))?;
+    write_new_file(
+        &dir,
+        &file_stem(&palette.name),
+        format.extension(),
+        &format.write(palette),
+    )
+    .map_err(PaletteError::Io)
+}
+
+// 同名文件已存在时依次尝试 "名字 (2)"、"名字 (3)"，不覆盖已有文件
+fn write_new_file(
+    dir: &Path,
+    stem: &str,
+    extension: &str,
+    bytes: &[u8],
+) -> std::io::Result<PathBuf> {
+    let mut path = dir.join(format!("{}.{}", stem, extension));
+    let mut n = 1;
+    loop {
+        match std::fs::File::create_new(&path) {
+            Ok(mut file) => {
+                file.write_all(bytes)?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                n += 1;
+                path = dir.join(format!("{} ({}).{}", stem, n, extension));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// 去掉 Windows 和 Unix 文件名中不能用的字符
+fn file_stem(name: &str) -> String {
+    let stem = name
+        .trim()
+        .replace(|c: char| c.is_control() || "/\\:*?\"<>|".contains(c), "_");
+    match stem.trim_end_matches(['.', ' ']) {
+        "" => "palette".to_string(),
+        stem => stem.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::palette::{file_stem, write_new_file};
+
+    #[test]
+    fn file_stem_strips_reserved_characters() {
+        assert_eq!(file_stem("a/b\\c:d*e?f\"g<h>i|j"), "a_b_c_d_e_f_g_h_i_j");
+        assert_eq!(file_stem("tab\there\nnew"), "tab_here_new");
+        assert_eq!(file_stem("  brand.  "), "brand");
+        assert_eq!(file_stem(" ... "), "palette");
+        assert_eq!(file_stem(""), "palette");
+    }
+
+    #[test]
+    fn write_new_file_does_not_overwrite() {
+        let dir = std::env::temp_dir().join(format!("colorose-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("brand.gpl"), "mine").unwrap();
+
+        let second = write_new_file(&dir, "brand", "gpl", b"second").unwrap();
+        let third = write_new_file(&dir, "brand", "gpl", b"third").unwrap();
+
+        assert_eq!(second, dir.join("brand (2).gpl"));
+        assert_eq!(third, dir.join("brand (3).gpl"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("brand.gpl")).unwrap(),
+            "mine"
+        );
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "second");
+        assert_eq!(std::fs::read_to_string(&third).unwrap(), "third");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
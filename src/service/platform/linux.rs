@@ -0,0 +1,120 @@
+use std::{collections::HashMap, error::Error};
+
+use raw_window_handle::RawWindowHandle;
+use x11rb::{
+    connection::Connection,
+    protocol::xproto::{ConnectionExt, Keycode},
+    rust_connection::RustConnection,
+};
+
+use crate::{
+    model::{hotkey::Hotkey, position::Position},
+    service::cursor_color::GetCursorColorError,
+    service::platform::Platform,
+};
+
+pub struct LinuxPlatform {
+    // 没有 X server（纯 Wayland 或无显示环境）时为 None
+    x11: Option<(RustConnection, usize)>,
+    keycodes: HashMap<u32, Vec<Keycode>>, // keysym 对应的键码
+}
+
+const XK_SHIFT: [u32; 2] = [0xffe1, 0xffe2];
+const XK_CONTROL: [u32; 2] = [0xffe3, 0xffe4];
+const XK_ALT: [u32; 2] = [0xffe9, 0xffea];
+
+impl LinuxPlatform {
+    pub fn new() -> Self {
+        let x11 = RustConnection::connect(None).ok();
+        let keycodes = x11
+            .as_ref()
+            .map(|(conn, _)| keycode_map(conn))
+            .unwrap_or_default();
+        LinuxPlatform { x11, keycodes }
+    }
+}
+
+fn keycode_map(conn: &RustConnection) -> HashMap<u32, Vec<Keycode>> {
+    let setup = conn.setup();
+    let (min, max) = (setup.min_keycode, setup.max_keycode);
+    let Some(reply) = conn
+        .get_keyboard_mapping(min, max - min + 1)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+    else {
+        return HashMap::new();
+    };
+
+    let mut map: HashMap<u32, Vec<Keycode>> = HashMap::new();
+    let per_keycode = reply.keysyms_per_keycode.max(1) as usize;
+    for (i, keysyms) in reply.keysyms.chunks(per_keycode).enumerate() {
+        for &keysym in keysyms.iter().filter(|keysym| **keysym != 0) {
+            let codes = map.entry(keysym).or_default();
+            let keycode = min + i as u8;
+            if !codes.contains(&keycode) {
+                codes.push(keycode);
+            }
+        }
+    }
+    map
+}
+
+impl Default for LinuxPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Platform for LinuxPlatform {
+    fn cursor_position(&self) -> Result<Position, GetCursorColorError> {
+        let (conn, screen_num) = self
+            .x11
+            .as_ref()
+            .ok_or(GetCursorColorError::UnableGetMousePosition)?;
+        let root = conn.setup().roots[*screen_num].root;
+        let reply = conn
+            .query_pointer(root)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .ok_or(GetCursorColorError::UnableGetMousePosition)?;
+
+        Ok(Position {
+            x: reply.root_x as i32,
+            y: reply.root_y as i32,
+        })
+    }
+
+    fn set_dpi_awareness(&self) -> Result<(), Box<dyn Error>> {
+        // X11 下不存在进程级 DPI 感知，缩放由 winit 处理
+        Ok(())
+    }
+
+    fn enable_backdrop(&self, _handle: RawWindowHandle) -> Result<(), Box<dyn Error>> {
+        // 模糊背景由合成器决定，这里保持透明窗口即可
+        Ok(())
+    }
+
+    fn is_hotkey_down(&self, hotkey: &Hotkey) -> Option<bool> {
+        // 纯 Wayland 下无法读取全局按键状态
+        let (conn, _) = self.x11.as_ref()?;
+        let keys = conn.query_keymap().ok()?.reply().ok()?.keys;
+        let down = |keysyms: &[u32]| {
+            keysyms.iter().any(|keysym| {
+                self.keycodes.get(keysym).is_some_and(|codes| {
+                    codes
+                        .iter()
+                        .any(|code| keys[*code as usize / 8] & (1 << (code % 8)) != 0)
+                })
+            })
+        };
+
+        // 字母的 keysym 与小写 ASCII 相同
+        let key = hotkey.key.to_ascii_lowercase() as u32;
+        Some(
+            down(&[key])
+                && down(&XK_CONTROL) == hotkey.ctrl
+                && down(&XK_SHIFT) == hotkey.shift
+                && down(&XK_ALT) == hotkey.alt,
+        )
+    }
+}
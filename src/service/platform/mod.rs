@@ -0,0 +1,36 @@
+use std::error::Error;
+
+use raw_window_handle::RawWindowHandle;
+
+use crate::{
+    model::{hotkey::Hotkey, position::Position},
+    service::cursor_color::GetCursorColorError,
+};
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[cfg(target_os = "linux")]
+pub use linux::LinuxPlatform as CurrentPlatform;
+#[cfg(target_os = "windows")]
+pub use windows::WindowsPlatform as CurrentPlatform;
+
+pub trait Platform: Send {
+    fn cursor_position(&self) -> Result<Position, GetCursorColorError>;
+
+    fn set_dpi_awareness(&self) -> Result<(), Box<dyn Error>>;
+
+    fn enable_backdrop(&self, handle: RawWindowHandle) -> Result<(), Box<dyn Error>>;
+
+    // 快捷键当前是否按下，不依赖窗口焦点；无法全局读取按键状态时返回 None
+    fn is_hotkey_down(&self, hotkey: &Hotkey) -> Option<bool>;
+
+    // 命令行模式下把输出接到启动它的终端，只有 Windows 的窗口程序需要
+    fn attach_console(&self) {}
+}
+
+pub fn current_platform() -> CurrentPlatform {
+    CurrentPlatform::new()
+}
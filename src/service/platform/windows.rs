@@ -0,0 +1,94 @@
+use std::error::Error;
+
+use raw_window_handle::RawWindowHandle;
+use windows::Win32::{
+    Foundation::{HWND, POINT},
+    Graphics::Dwm::{DWMWINDOWATTRIBUTE, DwmSetWindowAttribute},
+    System::Console::{ATTACH_PARENT_PROCESS, AttachConsole},
+    UI::{
+        HiDpi::{PROCESS_PER_MONITOR_DPI_AWARE, SetProcessDpiAwareness},
+        Input::KeyboardAndMouse::{GetAsyncKeyState, VK_CONTROL, VK_MENU, VK_SHIFT},
+        WindowsAndMessaging::GetCursorPos,
+    },
+};
+
+use crate::{
+    model::{hotkey::Hotkey, position::Position},
+    service::cursor_color::GetCursorColorError,
+    service::platform::Platform,
+};
+
+pub struct WindowsPlatform;
+
+impl WindowsPlatform {
+    pub fn new() -> Self {
+        WindowsPlatform
+    }
+}
+
+impl Default for WindowsPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Platform for WindowsPlatform {
+    fn cursor_position(&self) -> Result<Position, GetCursorColorError> {
+        unsafe {
+            let mut point = POINT::default();
+            match GetCursorPos(&mut point) {
+                Ok(_) => Ok(Position {
+                    x: point.x,
+                    y: point.y,
+                }),
+                Err(_) => Err(GetCursorColorError::UnableGetMousePosition),
+            }
+        }
+    }
+
+    fn set_dpi_awareness(&self) -> Result<(), Box<dyn Error>> {
+        unsafe {
+            SetProcessDpiAwareness(PROCESS_PER_MONITOR_DPI_AWARE)?;
+        }
+        Ok(())
+    }
+
+    fn enable_backdrop(&self, handle: RawWindowHandle) -> Result<(), Box<dyn Error>> {
+        let RawWindowHandle::Win32(handle) = handle else {
+            return Ok(());
+        };
+        let hwnd = HWND(handle.hwnd.get() as *mut _);
+
+        unsafe {
+            const DWMWA_SYSTEMBACKDROP_TYPE: DWMWINDOWATTRIBUTE = DWMWINDOWATTRIBUTE(38);
+            const DWMSBT_ACRYLIC: u32 = 2; // 亚克力效果
+
+            DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_SYSTEMBACKDROP_TYPE,
+                &DWMSBT_ACRYLIC as *const _ as *const _,
+                std::mem::size_of_val(&DWMSBT_ACRYLIC) as u32,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn is_hotkey_down(&self, hotkey: &Hotkey) -> Option<bool> {
+        // 最高位表示按键当前是否按下，字母和数字的虚拟键码与大写 ASCII 相同
+        let down = |vk: i32| unsafe { GetAsyncKeyState(vk) as u16 & 0x8000 != 0 };
+        Some(
+            down(hotkey.key.to_ascii_uppercase() as i32)
+                && down(VK_CONTROL.0 as i32) == hotkey.ctrl
+                && down(VK_SHIFT.0 as i32) == hotkey.shift
+                && down(VK_MENU.0 as i32) == hotkey.alt,
+        )
+    }
+
+    // 程序是窗口子系统，从终端启动时没有控制台，附加到父进程的控制台上；失败时输出只能重定向
+    fn attach_console(&self) {
+        unsafe {
+            let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+        }
+    }
+}
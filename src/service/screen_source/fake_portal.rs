@@ -0,0 +1,154 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+};
+
+use image::RgbaImage;
+use zbus::{
+    blocking::{self, connection},
+    fdo,
+    message::Header,
+    zvariant::{OwnedObjectPath, OwnedValue, Value},
+};
+
+use crate::service::screen_source::portal::{
+    PORTAL_DESTINATION, PORTAL_PATH, PortalScreenSource, REQUEST_INTERFACE,
+};
+
+// 在私有的 D-Bus session bus 上模拟 xdg-desktop-portal 的 Screenshot 接口，
+// 用来在没有合成器的环境下测试 PortalScreenSource
+pub struct FakePortal {
+    daemon: Child,
+    address: String,
+    screenshot_path: PathBuf,
+    _conn: blocking::Connection,
+}
+
+struct FakeScreenshot {
+    color: (f64, f64, f64),
+    uri: String,
+}
+
+#[zbus::interface(name = "org.freedesktop.portal.Screenshot")]
+impl FakeScreenshot {
+    async fn pick_color(
+        &self,
+        #[zbus(header)] header: Header<'_>,
+        #[zbus(connection)] conn: &zbus::Connection,
+        _parent_window: &str,
+        options: HashMap<String, OwnedValue>,
+    ) -> fdo::Result<OwnedObjectPath> {
+        let results = HashMap::from([("color", Value::from(self.color))]);
+        respond(&header, conn, &options, results).await
+    }
+
+    async fn screenshot(
+        &self,
+        #[zbus(header)] header: Header<'_>,
+        #[zbus(connection)] conn: &zbus::Connection,
+        _parent_window: &str,
+        options: HashMap<String, OwnedValue>,
+    ) -> fdo::Result<OwnedObjectPath> {
+        let results = HashMap::from([("uri", Value::from(self.uri.as_str()))]);
+        respond(&header, conn, &options, results).await
+    }
+}
+
+// 按门户的约定，在 request/<sender>/<handle_token> 上发出 Response 信号
+async fn respond(
+    header: &Header<'_>,
+    conn: &zbus::Connection,
+    options: &HashMap<String, OwnedValue>,
+    results: HashMap<&str, Value<'_>>,
+) -> fdo::Result<OwnedObjectPath> {
+    let sender = header
+        .sender()
+        .ok_or(fdo::Error::Failed("missing sender".into()))?;
+    let token = options
+        .get("handle_token")
+        .and_then(|value| value.downcast_ref::<String>().ok())
+        .ok_or(fdo::Error::InvalidArgs("missing handle_token".into()))?;
+    let path = format!(
+        "{PORTAL_PATH}/request/{}/{token}",
+        sender.trim_start_matches(':').replace('.', "_")
+    );
+
+    conn.emit_signal(
+        Some(sender.as_str()),
+        path.as_str(),
+        REQUEST_INTERFACE,
+        "Response",
+        &(0u32, results),
+    )
+    .await?;
+    OwnedObjectPath::try_from(path).map_err(|e| fdo::Error::Failed(e.to_string()))
+}
+
+impl FakePortal {
+    // 启动一个 dbus-daemon，并在上面注册门户服务
+    // PickColor 总是返回 color，Screenshot 总是返回 screenshot
+    pub fn spawn(color: (f64, f64, f64), screenshot: &RgbaImage) -> Option<Self> {
+        let mut daemon = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address=1"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let mut address = String::new();
+        BufReader::new(daemon.stdout.take()?)
+            .read_line(&mut address)
+            .ok()?;
+        let address = address.trim().to_string();
+
+        let screenshot_path = std::env::temp_dir().join(format!(
+            "colorose-fake-portal-{}-{}.png",
+            std::process::id(),
+            daemon.id()
+        ));
+        screenshot.save(&screenshot_path).ok()?;
+
+        let conn = connection::Builder::address(address.as_str())
+            .ok()?
+            .name(PORTAL_DESTINATION)
+            .ok()?
+            .serve_at(
+                PORTAL_PATH,
+                FakeScreenshot {
+                    color,
+                    uri: format!("file://{}", screenshot_path.display()),
+                },
+            )
+            .ok()?
+            .build()
+            .ok()?;
+
+        Some(FakePortal {
+            daemon,
+            address,
+            screenshot_path,
+            _conn: conn,
+        })
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn client(&self) -> Option<PortalScreenSource> {
+        let conn = connection::Builder::address(self.address.as_str())
+            .ok()?
+            .build()
+            .ok()?;
+        Some(PortalScreenSource::with_connection(conn))
+    }
+}
+
+impl Drop for FakePortal {
+    fn drop(&mut self) {
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+        let _ = std::fs::remove_file(&self.screenshot_path);
+    }
+}
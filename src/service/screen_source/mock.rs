@@ -0,0 +1,206 @@
+use image::{Rgba, RgbaImage};
+
+use crate::{
+    model::position::Position,
+    service::{
+        cursor_color::GetCursorColorError,
+        screen_source::{Monitor, ScreenSource},
+    },
+};
+
+// 内存中的假屏幕，image 覆盖整个虚拟桌面（物理像素）
+// image 的 (0, 0) 对应物理坐标 origin
+pub struct MockScreenSource {
+    pub image: RgbaImage,
+    pub origin: Position,
+    pub monitors: Vec<Monitor>,
+    pub cursor: Position,
+}
+
+impl MockScreenSource {
+    // 单显示器，大小和 image 相同，缩放为 1
+    pub fn new(image: RgbaImage) -> Self {
+        let (width, height) = image.dimensions();
+        MockScreenSource {
+            image,
+            origin: Position::default(),
+            monitors: vec![Monitor {
+                id: 0,
+                x: 0,
+                y: 0,
+                width,
+                height,
+                scale_factor: 1.0,
+            }],
+            cursor: Position::default(),
+        }
+    }
+
+    pub fn with_monitors(mut self, monitors: Vec<Monitor>) -> Self {
+        self.monitors = monitors;
+        self
+    }
+
+    pub fn with_origin(mut self, origin: Position) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn with_cursor(mut self, cursor: Position) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    pub fn set_cursor_position(&mut self, cursor: Position) {
+        self.cursor = cursor;
+    }
+
+    fn physical_pixel(&self, x: i32, y: i32) -> Option<Rgba<u8>> {
+        let x = u32::try_from(x - self.origin.x).ok()?;
+        let y = u32::try_from(y - self.origin.y).ok()?;
+        self.image.get_pixel_checked(x, y).copied()
+    }
+}
+
+impl ScreenSource for MockScreenSource {
+    fn monitors(&self) -> Result<Vec<Monitor>, GetCursorColorError> {
+        Ok(self.monitors.clone())
+    }
+
+    fn cursor_position(&self) -> Result<Position, GetCursorColorError> {
+        Ok(self.cursor)
+    }
+
+    fn capture_area(
+        &self,
+        monitor: &Monitor,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> Result<RgbaImage, GetCursorColorError> {
+        let scale = monitor.scale_factor;
+        let monitor_x = (monitor.x as f32 * scale).round() as i32;
+        let monitor_y = (monitor.y as f32 * scale).round() as i32;
+        let monitor_width = (monitor.width as f32 * scale).round() as i32;
+        let monitor_height = (monitor.height as f32 * scale).round() as i32;
+
+        Ok(RgbaImage::from_fn(width, height, |dx, dy| {
+            let local_x = x + dx as i32;
+            let local_y = y + dy as i32;
+            if (0..monitor_width).contains(&local_x) && (0..monitor_height).contains(&local_y) {
+                self.physical_pixel(monitor_x + local_x, monitor_y + local_y)
+                    .unwrap_or(Rgba([0, 0, 0, 0]))
+            } else {
+                Rgba([0, 0, 0, 0])
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use crate::{
+        model::{
+            color::Color, position::Position, sample_aggregator::SampleAggregator,
+            sample_shape::SampleShape, sample_space::SampleSpace,
+        },
+        service::{
+            cursor_color::{GetCursorColorError, SampleSettings, get_screen_data},
+            screen_source::{Monitor, mock::MockScreenSource},
+        },
+    };
+
+    const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+    fn settings(size: usize, aggregator: SampleAggregator) -> SampleSettings {
+        SampleSettings {
+            tex_size: size,
+            sample_size: size,
+            shape: SampleShape::Square,
+            aggregator,
+            space: SampleSpace::SRGB,
+        }
+    }
+
+    fn monitor(id: u32, x: i32, width: u32, scale_factor: f32) -> Monitor {
+        Monitor {
+            id,
+            x,
+            y: 0,
+            width,
+            height: 100,
+            scale_factor,
+        }
+    }
+
+    fn sample_at(source: &MockScreenSource, x: i32, y: i32, size: usize) -> Color {
+        get_screen_data(
+            source,
+            Position { x, y },
+            &settings(size, SampleAggregator::Mean),
+        )
+        .unwrap()
+        .cursor_pixel_color
+    }
+
+    #[test]
+    fn averages_the_sample_area() {
+        // 左半黑右半白，3×3 中一列黑两列白
+        let image = RgbaImage::from_fn(10, 10, |x, _| if x < 5 { BLACK } else { WHITE });
+        let source = MockScreenSource::new(image);
+        assert_eq!(sample_at(&source, 5, 5, 3), Color::new(170, 170, 170));
+        assert_eq!(sample_at(&source, 5, 5, 1), Color::new(255, 255, 255));
+        assert_eq!(sample_at(&source, 4, 5, 1), Color::new(0, 0, 0));
+    }
+
+    #[test]
+    fn finds_the_monitor_under_the_cursor() {
+        // 第二个显示器缩放为 2，物理坐标从 200 开始，中间 100~200 没有显示器
+        let image = RgbaImage::from_fn(300, 200, |x, _| match x {
+            0..100 => Rgba([255, 0, 0, 255]),
+            200.. => Rgba([0, 0, 255, 255]),
+            _ => Rgba([0, 255, 0, 255]),
+        });
+        let source = MockScreenSource::new(image)
+            .with_monitors(vec![monitor(0, 0, 100, 1.0), monitor(1, 100, 50, 2.0)]);
+        assert_eq!(sample_at(&source, 50, 50, 3), Color::new(255, 0, 0));
+        assert_eq!(sample_at(&source, 250, 150, 3), Color::new(0, 0, 255));
+        assert!(matches!(
+            get_screen_data(
+                &source,
+                Position { x: 150, y: 50 },
+                &settings(3, SampleAggregator::Mean)
+            ),
+            Err(GetCursorColorError::ScreenCountIsZero)
+        ));
+    }
+
+    #[test]
+    fn ignores_pixels_off_the_screen() {
+        let source = MockScreenSource::new(RgbaImage::from_pixel(100, 100, WHITE));
+        let white = Color::new(255, 255, 255);
+        for (x, y) in [(0, 0), (99, 50), (50, 99), (99, 99)] {
+            assert_eq!(sample_at(&source, x, y, 5), white, "at ({}, {})", x, y);
+        }
+        for aggregator in SampleAggregator::ALL {
+            let data = get_screen_data(&source, Position { x: 0, y: 0 }, &settings(5, aggregator))
+                .unwrap();
+            assert_eq!(data.cursor_pixel_color, white, "{:?}", aggregator);
+            assert_eq!(data.sample_stats.min, white);
+        }
+    }
+
+    #[test]
+    fn ignores_pixels_of_the_next_monitor() {
+        // 两个相邻的显示器，边缘的采样不混入另一个显示器的像素
+        let image = RgbaImage::from_fn(200, 100, |x, _| if x < 100 { WHITE } else { BLACK });
+        let source = MockScreenSource::new(image)
+            .with_monitors(vec![monitor(0, 0, 100, 1.0), monitor(1, 100, 100, 1.0)]);
+        assert_eq!(sample_at(&source, 99, 50, 5), Color::new(255, 255, 255));
+        assert_eq!(sample_at(&source, 100, 50, 5), Color::new(0, 0, 0));
+    }
+}
@@ -0,0 +1,96 @@
+use image::RgbaImage;
+
+use crate::{
+    model::{color::Color, position::Position},
+    service::cursor_color::GetCursorColorError,
+};
+
+#[cfg(all(test, target_os = "linux"))]
+pub mod fake_portal;
+#[cfg(test)]
+pub mod mock;
+#[cfg(target_os = "linux")]
+pub mod portal;
+pub mod screenshots_source;
+#[cfg(target_os = "linux")]
+pub mod x11;
+
+#[cfg(target_os = "linux")]
+pub use portal::PortalScreenSource;
+pub use screenshots_source::ScreenshotsSource;
+#[cfg(target_os = "linux")]
+pub use x11::X11ScreenSource;
+
+// 显示器信息，坐标和尺寸为逻辑像素，乘以 scale_factor 得到物理像素
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Monitor {
+    pub id: u32,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f32,
+}
+
+pub trait ScreenSource {
+    fn monitors(&self) -> Result<Vec<Monitor>, GetCursorColorError>;
+
+    fn cursor_position(&self) -> Result<Position, GetCursorColorError>;
+
+    // x, y 为相对显示器左上角的物理像素坐标
+    // 返回的图像总是 width * height，超出显示器的部分为透明黑色
+    fn capture_area(
+        &self,
+        monitor: &Monitor,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> Result<RgbaImage, GetCursorColorError>;
+
+    // 无法全局读取鼠标时（Wayland），需要用户点击按钮后调用 pick 取色
+    fn needs_explicit_pick(&self) -> bool {
+        false
+    }
+
+    fn pick(&self) -> Result<(), GetCursorColorError> {
+        Ok(())
+    }
+
+    // 只让用户点选一个颜色，不截图也不移动鼠标位置
+    fn pick_color(&self) -> Result<Color, GetCursorColorError> {
+        Err(GetCursorColorError::UnablePickColor)
+    }
+}
+
+// Linux 下 Wayland 会话使用 xdg-desktop-portal，X11 会话使用原生 X11 后端，
+// 都不可用时退回 screenshots
+pub fn default_screen_source() -> Box<dyn ScreenSource> {
+    #[cfg(target_os = "linux")]
+    {
+        let wayland = std::env::var("XDG_SESSION_TYPE").as_deref() == Ok("wayland");
+        if wayland && let Some(source) = PortalScreenSource::connect() {
+            return Box::new(source);
+        }
+        if !wayland && let Some(source) = X11ScreenSource::connect() {
+            return Box::new(source);
+        }
+    }
+    Box::new(ScreenshotsSource::new())
+}
+
+// 把 image 放到 width * height 的透明画布的 (offset_x, offset_y) 处
+pub fn pad_area(
+    image: RgbaImage,
+    offset_x: u32,
+    offset_y: u32,
+    width: u32,
+    height: u32,
+) -> RgbaImage {
+    if image.dimensions() == (width, height) {
+        return image;
+    }
+    let mut canvas = RgbaImage::new(width, height);
+    image::imageops::replace(&mut canvas, &image, offset_x as i64, offset_y as i64);
+    canvas
+}
@@ -0,0 +1,311 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+use image::RgbaImage;
+use percent_encoding::percent_decode_str;
+use zbus::{
+    blocking::{Connection, Proxy},
+    zvariant::{OwnedObjectPath, OwnedValue, Value},
+};
+
+use crate::{
+    model::{color::Color, position::Position},
+    service::{
+        cursor_color::GetCursorColorError,
+        screen_source::{Monitor, ScreenSource, pad_area},
+    },
+};
+
+pub const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+pub const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+pub const SCREENSHOT_INTERFACE: &str = "org.freedesktop.portal.Screenshot";
+pub const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+
+// Wayland 下不能全局读取鼠标和截屏，改为通过 xdg-desktop-portal：
+// PickColor 由用户点选颜色，再用一张 Screenshot 作为放大镜的画面
+// 鼠标位置是虚拟的，取色后定位到截图中最接近所选颜色的像素
+pub struct PortalScreenSource {
+    conn: Connection,
+    screenshot: RefCell<Option<RgbaImage>>,
+    cursor: Cell<Position>,
+    request_count: Cell<u32>,
+}
+
+impl PortalScreenSource {
+    pub fn connect() -> Option<Self> {
+        Connection::session().ok().map(Self::with_connection)
+    }
+
+    pub fn with_connection(conn: Connection) -> Self {
+        PortalScreenSource {
+            conn,
+            screenshot: RefCell::new(None),
+            cursor: Cell::new(Position::default()),
+            request_count: Cell::new(0),
+        }
+    }
+
+    // 调用 Screenshot 门户的方法，并等待对应 Request 对象的 Response 信号
+    fn request(
+        &self,
+        method: &str,
+        mut options: HashMap<&str, Value>,
+    ) -> Result<HashMap<String, OwnedValue>, GetCursorColorError> {
+        let err = |_| GetCursorColorError::UnablePickColor;
+
+        self.request_count.set(self.request_count.get() + 1);
+        let token = format!("colorose{}", self.request_count.get());
+        let sender = self
+            .conn
+            .unique_name()
+            .ok_or(GetCursorColorError::UnablePickColor)?
+            .trim_start_matches(':')
+            .replace('.', "_");
+        let request_path = format!("{PORTAL_PATH}/request/{sender}/{token}");
+
+        // 必须在调用方法之前订阅信号，否则可能错过响应
+        let request = Proxy::new(
+            &self.conn,
+            PORTAL_DESTINATION,
+            request_path.as_str(),
+            REQUEST_INTERFACE,
+        )
+        .map_err(err)?;
+        let mut responses = request.receive_signal("Response").map_err(err)?;
+
+        let portal = Proxy::new(
+            &self.conn,
+            PORTAL_DESTINATION,
+            PORTAL_PATH,
+            SCREENSHOT_INTERFACE,
+        )
+        .map_err(err)?;
+        options.insert("handle_token", Value::from(token));
+        let _: OwnedObjectPath = portal.call(method, &("", options)).map_err(err)?;
+
+        let message = responses
+            .next()
+            .ok_or(GetCursorColorError::UnablePickColor)?;
+        let (response, results): (u32, HashMap<String, OwnedValue>) =
+            message.body().deserialize().map_err(err)?;
+        // 0 成功，1 用户取消，2 其他错误
+        if response != 0 {
+            return Err(GetCursorColorError::UnablePickColor);
+        }
+        Ok(results)
+    }
+
+    fn refresh_screenshot(&self) -> Result<(), GetCursorColorError> {
+        let results = self.request(
+            "Screenshot",
+            HashMap::from([("interactive", Value::from(false))]),
+        )?;
+        let uri = results
+            .get("uri")
+            .and_then(|value| value.downcast_ref::<String>().ok())
+            .ok_or(GetCursorColorError::UnableGetBuffer)?;
+        let path = uri
+            .strip_prefix("file://")
+            .ok_or(GetCursorColorError::UnableGetBuffer)?;
+        let path = percent_decode_str(path)
+            .decode_utf8()
+            .or(Err(GetCursorColorError::UnableGetBuffer))?;
+        let image = image::open(path.as_ref())
+            .or(Err(GetCursorColorError::UnableGetBuffer))?
+            .to_rgba8();
+
+        *self.screenshot.borrow_mut() = Some(image);
+        Ok(())
+    }
+
+    // 在截图中找和 color 最接近的像素，相同时取离上一次位置最近的
+    fn locate(&self, color: Color) -> Option<Position> {
+        let screenshot = self.screenshot.borrow();
+        let cursor = self.cursor.get();
+        screenshot
+            .as_ref()?
+            .enumerate_pixels()
+            .min_by_key(|(x, y, pixel)| {
+                let dr = pixel[0] as i32 - color.r as i32;
+                let dg = pixel[1] as i32 - color.g as i32;
+                let db = pixel[2] as i32 - color.b as i32;
+                let distance = (*x as i32 - cursor.x).abs() + (*y as i32 - cursor.y).abs();
+                (dr * dr + dg * dg + db * db, distance)
+            })
+            .map(|(x, y, _)| Position {
+                x: x as i32,
+                y: y as i32,
+            })
+    }
+}
+
+impl ScreenSource for PortalScreenSource {
+    fn monitors(&self) -> Result<Vec<Monitor>, GetCursorColorError> {
+        // 门户截图覆盖整个桌面，当作一个显示器处理
+        let screenshot = self.screenshot.borrow();
+        let screenshot = screenshot
+            .as_ref()
+            .ok_or(GetCursorColorError::ScreenCountIsZero)?;
+        Ok(vec![Monitor {
+            id: 0,
+            x: 0,
+            y: 0,
+            width: screenshot.width(),
+            height: screenshot.height(),
+            scale_factor: 1.0,
+        }])
+    }
+
+    fn cursor_position(&self) -> Result<Position, GetCursorColorError> {
+        Ok(self.cursor.get())
+    }
+
+    fn capture_area(
+        &self,
+        _monitor: &Monitor,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> Result<RgbaImage, GetCursorColorError> {
+        let screenshot = self.screenshot.borrow();
+        let screenshot = screenshot
+            .as_ref()
+            .ok_or(GetCursorColorError::UnableGetBuffer)?;
+
+        let min_x = x.max(0);
+        let min_y = y.max(0);
+        let max_x = (x + width as i32).min(screenshot.width() as i32);
+        let max_y = (y + height as i32).min(screenshot.height() as i32);
+        if min_x >= max_x || min_y >= max_y {
+            return Err(GetCursorColorError::UnableGetBuffer);
+        }
+        let image = image::imageops::crop_imm(
+            screenshot,
+            min_x as u32,
+            min_y as u32,
+            (max_x - min_x) as u32,
+            (max_y - min_y) as u32,
+        )
+        .to_image();
+        Ok(pad_area(
+            image,
+            (min_x - x) as u32,
+            (min_y - y) as u32,
+            width,
+            height,
+        ))
+    }
+
+    fn needs_explicit_pick(&self) -> bool {
+        true
+    }
+
+    fn pick(&self) -> Result<(), GetCursorColorError> {
+        let color = self.pick_color()?;
+        self.refresh_screenshot()?;
+        if let Some(position) = self.locate(color) {
+            self.cursor.set(position);
+        }
+        Ok(())
+    }
+
+    fn pick_color(&self) -> Result<Color, GetCursorColorError> {
+        let results = self.request("PickColor", HashMap::new())?;
+        let value = results
+            .get("color")
+            .ok_or(GetCursorColorError::UnablePickColor)?;
+        let (r, g, b) = <(f64, f64, f64)>::try_from(
+            value
+                .try_clone()
+                .or(Err(GetCursorColorError::UnablePickColor))?,
+        )
+        .or(Err(GetCursorColorError::UnablePickColor))?;
+
+        let to_u8 = |x: f64| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Ok(Color::new(to_u8(r), to_u8(g), to_u8(b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use crate::{
+        model::{
+            color::Color, sample_aggregator::SampleAggregator, sample_shape::SampleShape,
+            sample_space::SampleSpace,
+        },
+        service::{
+            cursor_color::{SampleSettings, get_screen_data},
+            screen_source::{ScreenSource, fake_portal::FakePortal},
+        },
+    };
+
+    // 灰色背景上 (30, 20) 处有一个 3×3 的橙色块
+    fn screenshot() -> RgbaImage {
+        RgbaImage::from_fn(64, 48, |x, y| {
+            if (29..=31).contains(&x) && (19..=21).contains(&y) {
+                Rgba([255, 128, 0, 255])
+            } else {
+                Rgba([40, 40, 40, 255])
+            }
+        })
+    }
+
+    fn spawn() -> FakePortal {
+        FakePortal::spawn((1.0, 128.0 / 255.0, 0.0), &screenshot()).expect("needs dbus-daemon")
+    }
+
+    #[test]
+    fn pick_color_returns_the_portal_color() {
+        let portal = spawn();
+        let source = portal.client().unwrap();
+        assert!(source.needs_explicit_pick());
+        assert_eq!(source.pick_color().unwrap(), Color::new(255, 128, 0));
+        // 只取色时不截图
+        assert!(source.monitors().is_err());
+    }
+
+    #[test]
+    fn pick_locates_the_color_in_the_screenshot() {
+        let portal = spawn();
+        let source = portal.client().unwrap();
+        source.pick().unwrap();
+
+        let monitors = source.monitors().unwrap();
+        assert_eq!((monitors[0].width, monitors[0].height), (64, 48));
+        let position = source.cursor_position().unwrap();
+        assert!((29..=31).contains(&position.x) && (19..=21).contains(&position.y));
+
+        let settings = SampleSettings {
+            tex_size: 3,
+            sample_size: 1,
+            shape: SampleShape::Square,
+            aggregator: SampleAggregator::Mean,
+            space: SampleSpace::SRGB,
+        };
+        let data = get_screen_data(&source, position, &settings).unwrap();
+        assert_eq!(data.cursor_pixel_color, Color::new(255, 128, 0));
+    }
+
+    #[test]
+    fn captures_areas_of_the_screenshot() {
+        let portal = spawn();
+        let source = portal.client().unwrap();
+        source.pick().unwrap();
+        let monitor = source.monitors().unwrap()[0];
+
+        let image = source.capture_area(&monitor, 29, 19, 3, 3).unwrap();
+        assert!(
+            image
+                .pixels()
+                .all(|pixel| *pixel == Rgba([255, 128, 0, 255]))
+        );
+        let image = source.capture_area(&monitor, -1, -1, 3, 3).unwrap();
+        assert_eq!(*image.get_pixel(0, 0), Rgba([0, 0, 0, 0]));
+        assert_eq!(*image.get_pixel(1, 1), Rgba([40, 40, 40, 255]));
+    }
+}
@@ -0,0 +1,87 @@
+use std::cell::RefCell;
+
+use image::RgbaImage;
+use screenshots::Screen;
+
+use crate::{
+    model::position::Position,
+    service::{
+        cursor_color::GetCursorColorError,
+        platform::{CurrentPlatform, Platform, current_platform},
+        screen_source::{Monitor, ScreenSource, pad_area},
+    },
+};
+
+// 基于 screenshots 库的截屏，鼠标位置由当前平台提供
+pub struct ScreenshotsSource {
+    platform: CurrentPlatform,
+    screens: RefCell<Vec<Screen>>,
+}
+
+impl ScreenshotsSource {
+    pub fn new() -> Self {
+        ScreenshotsSource {
+            platform: current_platform(),
+            screens: RefCell::new(vec![]),
+        }
+    }
+}
+
+impl Default for ScreenshotsSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScreenSource for ScreenshotsSource {
+    fn monitors(&self) -> Result<Vec<Monitor>, GetCursorColorError> {
+        let screens = Screen::all().or(Err(GetCursorColorError::UnableGetScreens))?;
+        let monitors = screens
+            .iter()
+            .map(|screen| Monitor {
+                id: screen.display_info.id,
+                x: screen.display_info.x,
+                y: screen.display_info.y,
+                width: screen.display_info.width,
+                height: screen.display_info.height,
+                scale_factor: screen.display_info.scale_factor,
+            })
+            .collect();
+        *self.screens.borrow_mut() = screens;
+        Ok(monitors)
+    }
+
+    fn cursor_position(&self) -> Result<Position, GetCursorColorError> {
+        self.platform.cursor_position()
+    }
+
+    fn capture_area(
+        &self,
+        monitor: &Monitor,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> Result<RgbaImage, GetCursorColorError> {
+        let screens = self.screens.borrow();
+        let screen = screens
+            .iter()
+            .find(|screen| screen.display_info.id == monitor.id)
+            .ok_or(GetCursorColorError::UnableGetScreens)?;
+        let image = screen
+            .capture_area(x, y, width, height)
+            .or(Err(GetCursorColorError::UnableGetBuffer))?;
+
+        // screenshots 会把区域裁剪到屏幕内，这里补回请求的大小
+        let (image_width, image_height) = image.dimensions();
+        let image = RgbaImage::from_raw(image_width, image_height, image.into_raw())
+            .ok_or(GetCursorColorError::UnableGetBuffer)?;
+        Ok(pad_area(
+            image,
+            (-x).max(0) as u32,
+            (-y).max(0) as u32,
+            width,
+            height,
+        ))
+    }
+}
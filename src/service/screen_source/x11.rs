@@ -0,0 +1,459 @@
+use std::{cell::RefCell, os::fd::AsRawFd, ptr::NonNull};
+
+use image::{Rgba, RgbaImage};
+use x11rb::{
+    connection::{Connection, RequestConnection},
+    protocol::{
+        randr::ConnectionExt as _,
+        shm::{self, ConnectionExt as _},
+        xinerama::{self, ConnectionExt as _},
+        xproto::{ConnectionExt as _, ImageFormat, ImageOrder},
+    },
+    rust_connection::RustConnection,
+};
+
+use crate::{
+    model::position::Position,
+    service::{
+        cursor_color::GetCursorColorError,
+        screen_source::{Monitor, ScreenSource, pad_area},
+    },
+};
+
+// 直接通过 X11 协议截屏：XQueryPointer 读鼠标，MIT-SHM 或 GetImage 读像素
+// X11 的坐标都是物理像素，所以显示器的 scale_factor 恒为 1
+pub struct X11ScreenSource {
+    conn: RustConnection,
+    screen_num: usize,
+    pixel_format: PixelFormat,
+    shm: RefCell<Option<ShmSegment>>,
+    shm_available: bool,
+}
+
+struct PixelFormat {
+    byte_order: ImageOrder,
+    red_mask: u32,
+    green_mask: u32,
+    blue_mask: u32,
+}
+
+impl PixelFormat {
+    fn decode(&self, bytes: &[u8]) -> Rgba<u8> {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        let pixel = match self.byte_order {
+            ImageOrder::MSB_FIRST => u32::from_be_bytes(bytes),
+            _ => u32::from_le_bytes(bytes),
+        };
+        let channel = |mask: u32| ((pixel & mask) >> mask.trailing_zeros()) as u8;
+        Rgba([
+            channel(self.red_mask),
+            channel(self.green_mask),
+            channel(self.blue_mask),
+            255,
+        ])
+    }
+}
+
+// 和 X server 共享的内存段，通过 fd 传递（MIT-SHM 1.2）
+struct ShmSegment {
+    seg: shm::Seg,
+    ptr: NonNull<u8>,
+    size: usize,
+}
+
+impl X11ScreenSource {
+    pub fn connect() -> Option<Self> {
+        let (conn, screen_num) = RustConnection::connect(None).ok()?;
+
+        let setup = conn.setup();
+        let screen = &setup.roots[screen_num];
+        // 只支持每像素 32 位的 TrueColor 格式，这也是几乎所有现代 X server 的默认值
+        let bits_per_pixel = setup
+            .pixmap_formats
+            .iter()
+            .find(|format| format.depth == screen.root_depth)?
+            .bits_per_pixel;
+        if bits_per_pixel != 32 {
+            return None;
+        }
+        let visual = screen
+            .allowed_depths
+            .iter()
+            .flat_map(|depth| depth.visuals.iter())
+            .find(|visual| visual.visual_id == screen.root_visual)?;
+        let pixel_format = PixelFormat {
+            byte_order: setup.image_byte_order,
+            red_mask: visual.red_mask,
+            green_mask: visual.green_mask,
+            blue_mask: visual.blue_mask,
+        };
+
+        let shm_available = conn
+            .shm_query_version()
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .is_some_and(|reply| (reply.major_version, reply.minor_version) >= (1, 2));
+
+        Some(X11ScreenSource {
+            conn,
+            screen_num,
+            pixel_format,
+            shm: RefCell::new(None),
+            shm_available,
+        })
+    }
+
+    fn root(&self) -> u32 {
+        self.conn.setup().roots[self.screen_num].root
+    }
+
+    fn randr_monitors(&self) -> Option<Vec<Monitor>> {
+        self.conn
+            .extension_information(x11rb::protocol::randr::X11_EXTENSION_NAME)
+            .ok()??;
+        let version = self.conn.randr_query_version(1, 5).ok()?.reply().ok()?;
+        if (version.major_version, version.minor_version) < (1, 5) {
+            return None;
+        }
+        let reply = self
+            .conn
+            .randr_get_monitors(self.root(), true)
+            .ok()?
+            .reply()
+            .ok()?;
+        let monitors: Vec<Monitor> = reply
+            .monitors
+            .iter()
+            .enumerate()
+            .map(|(id, monitor)| Monitor {
+                id: id as u32,
+                x: monitor.x as i32,
+                y: monitor.y as i32,
+                width: monitor.width as u32,
+                height: monitor.height as u32,
+                scale_factor: 1.0,
+            })
+            .collect();
+        (!monitors.is_empty()).then_some(monitors)
+    }
+
+    fn xinerama_monitors(&self) -> Option<Vec<Monitor>> {
+        self.conn
+            .extension_information(xinerama::X11_EXTENSION_NAME)
+            .ok()??;
+        let active = self.conn.xinerama_is_active().ok()?.reply().ok()?;
+        if active.state == 0 {
+            return None;
+        }
+        let reply = self.conn.xinerama_query_screens().ok()?.reply().ok()?;
+        let monitors: Vec<Monitor> = reply
+            .screen_info
+            .iter()
+            .enumerate()
+            .map(|(id, screen)| Monitor {
+                id: id as u32,
+                x: screen.x_org as i32,
+                y: screen.y_org as i32,
+                width: screen.width as u32,
+                height: screen.height as u32,
+                scale_factor: 1.0,
+            })
+            .collect();
+        (!monitors.is_empty()).then_some(monitors)
+    }
+
+    fn root_monitor(&self) -> Monitor {
+        let screen = &self.conn.setup().roots[self.screen_num];
+        Monitor {
+            id: 0,
+            x: 0,
+            y: 0,
+            width: screen.width_in_pixels as u32,
+            height: screen.height_in_pixels as u32,
+            scale_factor: 1.0,
+        }
+    }
+
+    // 读取根窗口上的一块区域，区域必须完全在根窗口内
+    fn get_image(&self, x: i16, y: i16, width: u16, height: u16) -> Option<Vec<u8>> {
+        let size = width as usize * height as usize * 4;
+        if self.shm_available
+            && let Some(data) = self.shm_get_image(x, y, width, height, size)
+        {
+            return Some(data);
+        }
+        let reply = self
+            .conn
+            .get_image(ImageFormat::Z_PIXMAP, self.root(), x, y, width, height, !0)
+            .ok()?
+            .reply()
+            .ok()?;
+        (reply.data.len() >= size).then_some(reply.data)
+    }
+
+    fn shm_get_image(
+        &self,
+        x: i16,
+        y: i16,
+        width: u16,
+        height: u16,
+        size: usize,
+    ) -> Option<Vec<u8>> {
+        let mut shm = self.shm.borrow_mut();
+        if shm.as_ref().is_none_or(|segment| segment.size < size) {
+            if let Some(segment) = shm.take() {
+                segment.release(&self.conn);
+            }
+            *shm = ShmSegment::create(&self.conn, size);
+        }
+        let segment = shm.as_ref()?;
+
+        self.conn
+            .shm_get_image(
+                self.root(),
+                x,
+                y,
+                width,
+                height,
+                !0,
+                ImageFormat::Z_PIXMAP.into(),
+                segment.seg,
+                0,
+            )
+            .ok()?
+            .reply()
+            .ok()?;
+        // SAFETY: 段在 create 时映射了 segment.size 字节，且 size <= segment.size
+        let data = unsafe { std::slice::from_raw_parts(segment.ptr.as_ptr(), size) };
+        Some(data.to_vec())
+    }
+}
+
+impl ShmSegment {
+    fn create(conn: &RustConnection, size: usize) -> Option<Self> {
+        let seg = conn.generate_id().ok()?;
+        let reply = conn
+            .shm_create_segment(seg, size as u32, false)
+            .ok()?
+            .reply()
+            .ok()?;
+        // SAFETY: fd 由 X server 创建，至少有 size 字节；映射在 release 时解除
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                size,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                reply.shm_fd.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            let _ = conn.shm_detach(seg);
+            return None;
+        }
+        Some(ShmSegment {
+            seg,
+            ptr: NonNull::new(ptr as *mut u8)?,
+            size,
+        })
+    }
+
+    fn release(self, conn: &RustConnection) {
+        // SAFETY: ptr 和 size 来自 create 中成功的 mmap
+        unsafe {
+            libc::munmap(self.ptr.as_ptr() as *mut _, self.size);
+        }
+        let _ = conn.shm_detach(self.seg);
+    }
+}
+
+impl Drop for X11ScreenSource {
+    fn drop(&mut self) {
+        if let Some(segment) = self.shm.get_mut().take() {
+            segment.release(&self.conn);
+        }
+        let _ = self.conn.flush();
+    }
+}
+
+impl ScreenSource for X11ScreenSource {
+    fn monitors(&self) -> Result<Vec<Monitor>, GetCursorColorError> {
+        Ok(self
+            .randr_monitors()
+            .or_else(|| self.xinerama_monitors())
+            .unwrap_or_else(|| vec![self.root_monitor()]))
+    }
+
+    fn cursor_position(&self) -> Result<Position, GetCursorColorError> {
+        let reply = self
+            .conn
+            .query_pointer(self.root())
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .ok_or(GetCursorColorError::UnableGetMousePosition)?;
+
+        Ok(Position {
+            x: reply.root_x as i32,
+            y: reply.root_y as i32,
+        })
+    }
+
+    fn capture_area(
+        &self,
+        monitor: &Monitor,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> Result<RgbaImage, GetCursorColorError> {
+        // 裁剪到显示器和根窗口内，GetImage 越界会返回 BadMatch
+        let root = self.root_monitor();
+        let min_x = (monitor.x + x).max(monitor.x).max(0);
+        let min_y = (monitor.y + y).max(monitor.y).max(0);
+        let max_x = (monitor.x + x + width as i32)
+            .min(monitor.x + monitor.width as i32)
+            .min(root.width as i32);
+        let max_y = (monitor.y + y + height as i32)
+            .min(monitor.y + monitor.height as i32)
+            .min(root.height as i32);
+        if min_x >= max_x || min_y >= max_y {
+            return Err(GetCursorColorError::UnableGetBuffer);
+        }
+
+        let clip_width = (max_x - min_x) as u32;
+        let clip_height = (max_y - min_y) as u32;
+        let data = self
+            .get_image(
+                min_x as i16,
+                min_y as i16,
+                clip_width as u16,
+                clip_height as u16,
+            )
+            .ok_or(GetCursorColorError::UnableGetBuffer)?;
+
+        let image = RgbaImage::from_fn(clip_width, clip_height, |px, py| {
+            let offset = ((py * clip_width + px) * 4) as usize;
+            self.pixel_format.decode(&data[offset..offset + 4])
+        });
+        Ok(pad_area(
+            image,
+            (min_x - monitor.x - x) as u32,
+            (min_y - monitor.y - y) as u32,
+            width,
+            height,
+        ))
+    }
+}
+
+// 需要 X server，在 Xvfb 中运行：
+// xvfb-run -s "-screen 0 640x480x24" cargo test -- --ignored x11
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+    use x11rb::{
+        COPY_DEPTH_FROM_PARENT, NONE,
+        connection::Connection,
+        protocol::xproto::{ConnectionExt as _, CreateWindowAux, WindowClass},
+    };
+
+    use crate::{
+        model::{
+            color::Color, position::Position, sample_aggregator::SampleAggregator,
+            sample_shape::SampleShape, sample_space::SampleSpace,
+        },
+        service::{
+            cursor_color::{SampleSettings, get_screen_data},
+            screen_source::{ScreenSource, x11::X11ScreenSource},
+        },
+    };
+
+    fn connect() -> X11ScreenSource {
+        X11ScreenSource::connect().expect("needs an X server with a 32-bit TrueColor visual")
+    }
+
+    // 在根窗口的 (x, y) 处放一个纯色窗口，等 X server 画完背景
+    fn show_window(source: &X11ScreenSource, x: i16, y: i16, rgb: [u8; 3]) {
+        let conn = &source.conn;
+        let format = &source.pixel_format;
+        let channel = |value: u8, mask: u32| ((value as u32) << mask.trailing_zeros()) & mask;
+        let pixel = channel(rgb[0], format.red_mask)
+            | channel(rgb[1], format.green_mask)
+            | channel(rgb[2], format.blue_mask);
+        let window = conn.generate_id().unwrap();
+        conn.create_window(
+            COPY_DEPTH_FROM_PARENT,
+            window,
+            source.root(),
+            x,
+            y,
+            40,
+            30,
+            0,
+            WindowClass::INPUT_OUTPUT,
+            0,
+            &CreateWindowAux::new()
+                .background_pixel(pixel)
+                .override_redirect(1),
+        )
+        .unwrap();
+        conn.map_window(window).unwrap();
+        conn.get_input_focus().unwrap().reply().unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs an X server, run under xvfb-run"]
+    fn reads_the_pointer_position() {
+        let source = connect();
+        source
+            .conn
+            .warp_pointer(NONE, source.root(), 0, 0, 0, 0, 123, 45)
+            .unwrap()
+            .check()
+            .unwrap();
+        assert_eq!(
+            source.cursor_position().unwrap(),
+            Position { x: 123, y: 45 }
+        );
+    }
+
+    #[test]
+    #[ignore = "needs an X server, run under xvfb-run"]
+    fn captures_with_shm_and_get_image() {
+        let mut source = connect();
+        show_window(&source, 10, 20, [0x33, 0x66, 0x99]);
+        let monitor = source.monitors().unwrap()[0];
+        let expected = Rgba([0x33, 0x66, 0x99, 255]);
+
+        assert!(source.shm_available, "Xvfb supports MIT-SHM 1.2");
+        assert!(source.shm_get_image(20, 30, 5, 5, 5 * 5 * 4).is_some());
+        let image = source.capture_area(&monitor, 20, 30, 5, 5).unwrap();
+        assert!(image.pixels().all(|pixel| *pixel == expected));
+
+        source.shm_available = false;
+        let image = source.capture_area(&monitor, 20, 30, 5, 5).unwrap();
+        assert!(image.pixels().all(|pixel| *pixel == expected));
+    }
+
+    #[test]
+    #[ignore = "needs an X server, run under xvfb-run"]
+    fn pads_and_samples_at_the_screen_corner() {
+        let source = connect();
+        show_window(&source, 0, 0, [0xCC, 0x33, 0x00]);
+        let monitor = source.monitors().unwrap()[0];
+        let image = source.capture_area(&monitor, -2, -2, 5, 5).unwrap();
+        assert_eq!(image.dimensions(), (5, 5));
+        assert_eq!(*image.get_pixel(0, 0), Rgba([0, 0, 0, 0]));
+        assert_eq!(*image.get_pixel(2, 2), Rgba([0xCC, 0x33, 0x00, 255]));
+
+        let settings = SampleSettings {
+            tex_size: 5,
+            sample_size: 5,
+            shape: SampleShape::Square,
+            aggregator: SampleAggregator::Mean,
+            space: SampleSpace::SRGB,
+        };
+        let data = get_screen_data(&source, Position { x: 0, y: 0 }, &settings).unwrap();
+        assert_eq!(data.cursor_pixel_color, Color::new(0xCC, 0x33, 0x00));
+    }
+}
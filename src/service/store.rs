@@ -0,0 +1,39 @@
+use std::{io, path::PathBuf};
+
+// 配置文件都放在 <配置目录>/colorose 下
+pub fn config_path(file: &str) -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("colorose").join(file))
+}
+
+// 没有配置目录或文件不存在时返回 None
+pub fn read_config(file: &str) -> io::Result<Option<String>> {
+    let Some(path) = config_path(file) else {
+        return Ok(None);
+    };
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// 没有配置目录时不保存
+pub fn write_config(file: &str, text: &str) -> io::Result<()> {
+    let Some(path) = config_path(file) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, text)
+}
+
+// 配置目录下某个子目录中的文件，按路径排序，目录不存在时为空
+pub fn config_files(dir: &str) -> Vec<PathBuf> {
+    let Some(entries) = config_path(dir).and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return vec![];
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    paths
+}
@@ -0,0 +1,20 @@
+use std::collections::BTreeMap;
+
+use crate::model::template::TemplatePreset;
+use crate::service::store;
+
+// 用户模板放在 <配置目录>/colorose/templates.json，格式为 {"名字": "模板"}
+const TEMPLATE_FILE: &str = "templates.json";
+
+// 文件不存在或无法解析时返回空，无效的模板跳过
+pub fn load_user_templates() -> Vec<TemplatePreset> {
+    let Ok(Some(text)) = store::read_config(TEMPLATE_FILE) else {
+        return vec![];
+    };
+    let Ok(map) = serde_json::from_str::<BTreeMap<String, String>>(&text) else {
+        return vec![];
+    };
+    map.iter()
+        .filter_map(|(name, source)| TemplatePreset::new(name.as_str(), source).ok())
+        .collect()
+}
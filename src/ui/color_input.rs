@@ -0,0 +1,68 @@
+use egui::{Color32, CornerRadius, RichText, Stroke, TextEdit, TextureHandle, Ui, Vec2};
+
+use crate::{model::color::Color, ui::swatch::show_swatch};
+
+const ERROR_COLOR: Color32 = Color32::from_rgb(220, 110, 110);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorInputTarget {
+    Reference,
+    Foreground,
+    Background,
+}
+
+// 输入或粘贴颜色字符串，点击按钮后返回解析出的颜色和用途
+pub fn show_color_input(
+    ui: &mut Ui,
+    checker_texture: &mut Option<TextureHandle>,
+    text: &mut String,
+    text_color: Color32,
+) -> Option<(Color, ColorInputTarget)> {
+    let parsed = text.parse::<Color>();
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        ui.add(
+            TextEdit::singleline(text)
+                .hint_text("#hex, rgb(), oklch(), name")
+                .desired_width(220.0),
+        );
+        if let Ok(color) = parsed {
+            show_swatch(
+                ui,
+                checker_texture,
+                color.to_color32(),
+                Vec2::new(15.0, 15.0),
+                CornerRadius::same(3),
+                Stroke::new(1.0, text_color),
+            );
+        }
+    });
+
+    match parsed {
+        Ok(color) => {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(color.to_hex_auto())
+                        .color(text_color)
+                        .strong(),
+                );
+                for (name, target) in [
+                    ("reference", ColorInputTarget::Reference),
+                    ("fg", ColorInputTarget::Foreground),
+                    ("bg", ColorInputTarget::Background),
+                ] {
+                    if ui.button(name).clicked() {
+                        action = Some((color, target));
+                    }
+                }
+            });
+        }
+        Err(e) if !text.trim().is_empty() => {
+            ui.label(RichText::new(e.to_string()).color(ERROR_COLOR));
+        }
+        Err(_) => {}
+    }
+
+    action
+}
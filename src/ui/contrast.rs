@@ -0,0 +1,84 @@
+use egui::{Color32, CornerRadius, Frame, Margin, RichText, Stroke, TextureHandle, Ui, Vec2};
+
+use crate::{model::color::Color, ui::swatch::show_swatch};
+
+const PASS_COLOR: Color32 = Color32::from_rgb(130, 200, 130);
+const FAIL_COLOR: Color32 = Color32::from_rgb(220, 110, 110);
+
+// 对比度面板，显示前景和背景的 WCAG 对比度与 APCA Lc
+pub fn show_contrast(
+    ui: &mut Ui,
+    checker_texture: &mut Option<TextureHandle>,
+    fg: &mut Color,
+    bg: &mut Color,
+    text_color: Color32,
+) {
+    let swatch_size = Vec2::new(15.0, 15.0);
+    let stroke = Stroke::new(1.0, text_color);
+
+    ui.horizontal(|ui| {
+        for (name, color) in [("fg", *fg), ("bg", *bg)] {
+            show_swatch(
+                ui,
+                checker_texture,
+                color.to_color32(),
+                swatch_size,
+                CornerRadius::same(3),
+                stroke,
+            );
+            ui.label(
+                RichText::new(format!("{} {}", name, color.to_hex()))
+                    .color(text_color)
+                    .strong(),
+            );
+        }
+        if ui.button("swap").clicked() {
+            std::mem::swap(fg, bg);
+        }
+    });
+
+    // 用前景色在背景色上写一段示例文字
+    Frame {
+        fill: bg.to_color32(),
+        inner_margin: Margin::symmetric(8, 4),
+        corner_radius: CornerRadius::same(5),
+        ..Default::default()
+    }
+    .show(ui, |ui| {
+        ui.label(RichText::new("The quick brown fox").color(fg.to_color32()));
+        ui.label(
+            RichText::new("Large text")
+                .size(20.0)
+                .color(fg.to_color32())
+                .strong(),
+        );
+    });
+
+    let wcag = fg.wcag_contrast(bg);
+    let level = |ui: &mut Ui, name: &str, pass: bool| {
+        ui.label(
+            RichText::new(name)
+                .color(if pass { PASS_COLOR } else { FAIL_COLOR })
+                .strong(),
+        );
+    };
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(format!("WCAG {:.2}:1", wcag.ratio))
+                .color(text_color)
+                .strong(),
+        );
+        ui.label(RichText::new("normal").color(text_color));
+        level(ui, "AA", wcag.aa_normal());
+        level(ui, "AAA", wcag.aaa_normal());
+        ui.label(RichText::new("large").color(text_color));
+        level(ui, "AA", wcag.aa_large());
+        level(ui, "AAA", wcag.aaa_large());
+    });
+    ui.label(
+        RichText::new(format!("APCA Lc {:.1}", fg.apca_contrast(bg)))
+            .color(text_color)
+            .strong(),
+    );
+    ui.label(RichText::new("press F / B to capture fg / bg").color(text_color));
+}
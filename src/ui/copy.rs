@@ -0,0 +1,43 @@
+use std::time::{Duration, Instant};
+
+use egui::{
+    Align2, Color32, Context, CornerRadius, CursorIcon, Frame, Id, Label, Margin, Order, RichText,
+    Sense, Ui, vec2,
+};
+
+const TOAST_DURATION: Duration = Duration::from_millis(1200);
+
+// 可以点击复制的读数，返回是否被点击
+pub fn show_copy_label(ui: &mut Ui, text: &str, text_color: Color32) -> bool {
+    ui.add(Label::new(RichText::new(text).color(text_color).strong()).sense(Sense::click()))
+        .on_hover_cursor(CursorIcon::PointingHand)
+        .clicked()
+}
+
+// 窗口底部短暂显示的提示，超时后清空
+pub fn show_toast(ctx: &Context, toast: &mut Option<(String, Instant)>, text_color: Color32) {
+    let Some((text, shown_at)) = toast else {
+        return;
+    };
+    if shown_at.elapsed() > TOAST_DURATION {
+        *toast = None;
+        return;
+    }
+
+    egui::Area::new(Id::new("toast"))
+        .order(Order::Foreground)
+        .anchor(Align2::CENTER_BOTTOM, vec2(0.0, -8.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            Frame {
+                fill: Color32::from_black_alpha(200),
+                inner_margin: Margin::symmetric(10, 4),
+                corner_radius: CornerRadius::same(5),
+                ..Default::default()
+            }
+            .show(ui, |ui| {
+                ui.set_max_width(300.0);
+                ui.add(Label::new(RichText::new(text.as_str()).color(text_color)).truncate());
+            });
+        });
+}
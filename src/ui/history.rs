@@ -0,0 +1,63 @@
+use egui::{Color32, CornerRadius, RichText, ScrollArea, Sense, Stroke, TextureHandle, Ui, Vec2};
+
+use crate::{model::history::History, ui::swatch::show_swatch};
+
+// 取色历史的色块条，新的在左边；点击返回颜色用于复制，右键删除
+pub fn show_history(
+    ui: &mut Ui,
+    checker_texture: &mut Option<TextureHandle>,
+    history: &mut History,
+    error: Option<&str>,
+    hint: &str,
+    text_color: Color32,
+) -> Option<String> {
+    if let Some(error) = error {
+        ui.label(RichText::new(error).color(text_color));
+    }
+    if history.entries().is_empty() {
+        ui.label(RichText::new(hint).color(text_color));
+        return None;
+    }
+
+    let mut clicked = None;
+    let mut remove = None;
+    let mut clear = false;
+    ScrollArea::horizontal()
+        .id_salt("history")
+        .max_width(ui.available_width())
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                for (index, entry) in history.entries().iter().enumerate().rev() {
+                    let response = show_swatch(
+                        ui,
+                        checker_texture,
+                        entry.color.to_color32(),
+                        Vec2::new(18.0, 18.0),
+                        CornerRadius::same(3),
+                        Stroke::new(1.0, text_color),
+                    )
+                    .interact(Sense::click())
+                    .on_hover_text(entry.to_string());
+                    if response.clicked() {
+                        clicked = Some(entry.color.to_hex_auto());
+                    }
+                    response.context_menu(|ui| {
+                        if ui.button("remove").clicked() {
+                            remove = Some(index);
+                        }
+                        if ui.button("clear history").clicked() {
+                            clear = true;
+                        }
+                    });
+                }
+            });
+        });
+
+    if let Some(index) = remove {
+        history.remove(index);
+    }
+    if clear {
+        history.clear();
+    }
+    clicked
+}
@@ -1,2 +1,10 @@
+pub mod color_input;
+pub mod contrast;
+pub mod copy;
+pub mod history;
+pub mod named_color;
+pub mod palette;
 pub mod screen;
+pub mod swatch;
+pub mod template;
 pub mod wheel;
@@ -0,0 +1,43 @@
+use egui::{Color32, Label, RichText, Sense, Ui};
+
+use crate::model::{color::Color, named_color::ColorDictionary};
+
+// 当前词典里最接近的颜色名，右键切换词典
+pub fn show_color_name(
+    ui: &mut Ui,
+    dictionaries: &[ColorDictionary],
+    dictionary_index: &mut usize,
+    message: Option<&str>,
+    color: &Color,
+    text_color: Color32,
+) {
+    let Some(dictionary) = dictionaries.get(*dictionary_index) else {
+        return;
+    };
+    let Some((named, delta_e)) = dictionary.nearest(color) else {
+        return;
+    };
+
+    ui.add(
+        Label::new(RichText::new(format!("≈{}", named.name)).color(text_color))
+            .truncate()
+            .sense(Sense::click()),
+    )
+    .on_hover_text(format!(
+        "{} {}\nΔE00:{:.2} ({})",
+        named.name,
+        named.color.to_hex(),
+        delta_e,
+        dictionary.name
+    ))
+    .context_menu(|ui| {
+        for (i, dictionary) in dictionaries.iter().enumerate() {
+            ui.radio_value(dictionary_index, i, &dictionary.name);
+        }
+        ui.separator();
+        ui.label("drop .json/.csv files to load");
+        if let Some(message) = message {
+            ui.label(message);
+        }
+    });
+}
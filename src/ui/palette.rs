@@ -0,0 +1,194 @@
+use egui::{
+    Button, Color32, CornerRadius, RichText, ScrollArea, Sense, Stroke, TextureHandle, Ui, Vec2,
+};
+
+use crate::{
+    model::{
+        color::Color,
+        palette::{
+            PaletteCollection, PaletteFormat, Swatch, code::CodeFormat, terminal::TerminalFormat,
+        },
+    },
+    ui::swatch::show_swatch,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteRequest {
+    Copy(String),
+    Export(usize, PaletteFormat),
+    CopyCode(usize, CodeFormat),
+    CopyTerminal(usize, TerminalFormat),
+}
+
+// 遍历色板时收集的修改，遍历结束后再应用
+enum PaletteAction {
+    Create,
+    Rename(usize, String),
+    Move(usize, usize),
+    Remove(usize),
+    AddColor(usize),
+    RenameSwatch(usize, usize, String),
+    MoveSwatch(usize, usize, usize),
+    RemoveSwatch(usize, usize),
+}
+
+// 色板侧栏，每个色板可折叠；点击颜色复制，右键标题或颜色编辑，右键标题导出文件或复制为代码
+pub fn show_palettes(
+    ui: &mut Ui,
+    checker_texture: &mut Option<TextureHandle>,
+    palettes: &mut PaletteCollection,
+    color: &Color,
+    error: Option<&str>,
+    text_color: Color32,
+) -> Option<PaletteRequest> {
+    let mut request = None;
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("palettes").color(text_color).strong());
+        if ui.button("new").clicked() {
+            action = Some(PaletteAction::Create);
+        }
+    });
+    if let Some(error) = error {
+        ui.label(RichText::new(error).color(text_color));
+    }
+    if palettes.palettes().is_empty() {
+        ui.label(
+            RichText::new("create a palette, then add the current color with +").color(text_color),
+        );
+    }
+
+    let count = palettes.palettes().len();
+    ScrollArea::vertical().id_salt("palettes").show(ui, |ui| {
+        for (index, palette) in palettes.palettes().iter().enumerate() {
+            let response = egui::CollapsingHeader::new(
+                RichText::new(&palette.name).color(text_color).strong(),
+            )
+            .id_salt(("palette", index))
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    let len = palette.swatches.len();
+                    for (swatch_index, swatch) in palette.swatches.iter().enumerate() {
+                        let response = show_swatch(
+                            ui,
+                            checker_texture,
+                            swatch.color.to_color32(),
+                            Vec2::new(18.0, 18.0),
+                            CornerRadius::same(3),
+                            Stroke::new(1.0, text_color),
+                        )
+                        .interact(Sense::click())
+                        .on_hover_text(swatch.to_string());
+                        if response.clicked() {
+                            request = Some(PaletteRequest::Copy(swatch.color.to_hex_auto()));
+                        }
+                        response.context_menu(|ui| {
+                            let mut name = swatch.name.clone();
+                            if ui
+                                .add(egui::TextEdit::singleline(&mut name).hint_text("name"))
+                                .changed()
+                            {
+                                action =
+                                    Some(PaletteAction::RenameSwatch(index, swatch_index, name));
+                            }
+                            if ui
+                                .add_enabled(swatch_index > 0, Button::new("move left"))
+                                .clicked()
+                            {
+                                action = Some(PaletteAction::MoveSwatch(
+                                    index,
+                                    swatch_index,
+                                    swatch_index - 1,
+                                ));
+                            }
+                            if ui
+                                .add_enabled(swatch_index + 1 < len, Button::new("move right"))
+                                .clicked()
+                            {
+                                action = Some(PaletteAction::MoveSwatch(
+                                    index,
+                                    swatch_index,
+                                    swatch_index + 1,
+                                ));
+                            }
+                            if ui.button("remove").clicked() {
+                                action = Some(PaletteAction::RemoveSwatch(index, swatch_index));
+                            }
+                        });
+                    }
+                    if ui
+                        .add(Button::new("+").min_size(Vec2::new(18.0, 18.0)))
+                        .on_hover_text(format!("add {}", color.to_hex_auto()))
+                        .clicked()
+                    {
+                        action = Some(PaletteAction::AddColor(index));
+                    }
+                });
+            });
+            response.header_response.context_menu(|ui| {
+                let mut name = palette.name.clone();
+                if ui.text_edit_singleline(&mut name).changed() {
+                    action = Some(PaletteAction::Rename(index, name));
+                }
+                if ui.add_enabled(index > 0, Button::new("move up")).clicked() {
+                    action = Some(PaletteAction::Move(index, index - 1));
+                }
+                if ui
+                    .add_enabled(index + 1 < count, Button::new("move down"))
+                    .clicked()
+                {
+                    action = Some(PaletteAction::Move(index, index + 1));
+                }
+                ui.menu_button("copy as", |ui| {
+                    for format in CodeFormat::ALL {
+                        if ui.button(format.name()).clicked() {
+                            request = Some(PaletteRequest::CopyCode(index, format));
+                        }
+                    }
+                    // 16~21 个颜色的色板才能作为终端配色
+                    ui.separator();
+                    let accepted = TerminalFormat::accepts(palette);
+                    for format in TerminalFormat::ALL {
+                        if ui
+                            .add_enabled(accepted, Button::new(format.name()))
+                            .on_disabled_hover_text("needs 16 to 21 colors")
+                            .clicked()
+                        {
+                            request = Some(PaletteRequest::CopyTerminal(index, format));
+                        }
+                    }
+                });
+                ui.menu_button("export", |ui| {
+                    for format in PaletteFormat::ALL {
+                        let text = format!("{} (.{})", format.name(), format.extension());
+                        if ui.button(text).clicked() {
+                            request = Some(PaletteRequest::Export(index, format));
+                        }
+                    }
+                });
+                if ui.button("delete").clicked() {
+                    action = Some(PaletteAction::Remove(index));
+                }
+            });
+        }
+    });
+
+    match action {
+        Some(PaletteAction::Create) => {
+            palettes.create();
+        }
+        Some(PaletteAction::Rename(index, name)) => palettes.rename(index, name),
+        Some(PaletteAction::Move(from, to)) => palettes.move_palette(from, to),
+        Some(PaletteAction::Remove(index)) => palettes.remove(index),
+        Some(PaletteAction::AddColor(index)) => palettes.add_swatch(index, Swatch::new("", *color)),
+        Some(PaletteAction::RenameSwatch(index, swatch, name)) => {
+            palettes.rename_swatch(index, swatch, name)
+        }
+        Some(PaletteAction::MoveSwatch(index, from, to)) => palettes.move_swatch(index, from, to),
+        Some(PaletteAction::RemoveSwatch(index, swatch)) => palettes.remove_swatch(index, swatch),
+        None => {}
+    }
+    request
+}
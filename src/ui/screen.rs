@@ -1,5 +1,11 @@
-use egui::{Color32, Frame, Margin, Pos2, Rect, Stroke, TextureHandle, TextureOptions, Ui, Vec2};
+use egui::{
+    Button, Color32, Frame, Margin, Pos2, Rect, Response, Stroke, TextureHandle, TextureOptions,
+    Ui, Vec2,
+};
 
+use crate::model::sample_shape::SampleShape;
+
+#[allow(clippy::too_many_arguments)]
 pub fn show_screen_img(
     ui: &mut Ui,
     texture: &mut Option<TextureHandle>,
@@ -8,6 +14,8 @@ pub fn show_screen_img(
     pixels: Vec<Color32>,
     color_revert: Color32,
     screen_sample_size: &mut usize,
+    sample_shape: SampleShape,
+    cursor_offset: (i32, i32),
     current_tex_size: &mut usize,
 ) {
     let current_screen_sample_size = *screen_sample_size;
@@ -52,13 +60,32 @@ pub fn show_screen_img(
             let max_x = (rect.max.x - rect.min.x) * max_factor + rect.min.x;
             let min_y = (rect.max.y - rect.min.y) * min_factor + rect.min.y;
             let max_y = (rect.max.y - rect.min.y) * max_factor + rect.min.y;
-            let square_rect = Rect::from_min_max(Pos2::new(min_x, min_y), Pos2::new(max_x, max_y));
-            painter.rect_stroke(
-                square_rect,
-                0.0,
-                Stroke::new(2.0, color_revert),
-                egui::StrokeKind::Outside,
-            );
+            let pixel_size = rect.width() / tex_size as f32;
+            let offset = Vec2::new(cursor_offset.0 as f32, cursor_offset.1 as f32) * pixel_size;
+            let square_rect = Rect::from_min_max(Pos2::new(min_x, min_y), Pos2::new(max_x, max_y))
+                .translate(offset);
+            let half = half_sample_size as i32;
+            let stroke = Stroke::new(2.0, color_revert);
+            match sample_shape {
+                SampleShape::Square => {
+                    painter.rect_stroke(square_rect, 0.0, stroke, egui::StrokeKind::Outside);
+                }
+                SampleShape::Circle => {
+                    let radius = SampleShape::radius(half) * pixel_size;
+                    painter.circle_stroke(square_rect.center(), radius, stroke);
+                }
+                SampleShape::Gaussian => {
+                    // 细线为采样范围，粗线为 1σ
+                    painter.rect_stroke(
+                        square_rect,
+                        0.0,
+                        Stroke::new(1.0, color_revert.gamma_multiply(0.5)),
+                        egui::StrokeKind::Outside,
+                    );
+                    let radius = SampleShape::sigma(half) * pixel_size;
+                    painter.circle_stroke(square_rect.center(), radius, stroke);
+                }
+            }
         });
     } else {
         Frame {
@@ -78,3 +105,32 @@ pub fn show_screen_img(
         });
     }
 }
+
+// 采样形状按钮，画出当前形状的图标
+pub fn show_shape_button(ui: &mut Ui, shape: SampleShape, color: Color32) -> Response {
+    let response = ui
+        .add(Button::new("").min_size(Vec2::new(20.0, 20.0)))
+        .on_hover_text(shape.name());
+    let center = response.rect.center();
+    let stroke = Stroke::new(1.5, color);
+    let painter = ui.painter();
+    match shape {
+        SampleShape::Square => {
+            painter.rect_stroke(
+                Rect::from_center_size(center, Vec2::splat(10.0)),
+                0.0,
+                stroke,
+                egui::StrokeKind::Middle,
+            );
+        }
+        SampleShape::Circle => {
+            painter.circle_stroke(center, 5.5, stroke);
+        }
+        SampleShape::Gaussian => {
+            painter.circle_filled(center, 6.0, color.gamma_multiply(0.25));
+            painter.circle_filled(center, 4.0, color.gamma_multiply(0.5));
+            painter.circle_filled(center, 2.0, color);
+        }
+    }
+    response
+}
@@ -0,0 +1,44 @@
+use egui::{
+    Color32, ColorImage, CornerRadius, Pos2, Rect, Response, Stroke, TextureHandle, TextureOptions,
+    Ui, Vec2, epaint::RectShape,
+};
+
+const CHECKER_CELL: f32 = 5.0; // 棋盘格每格的大小
+const CHECKER_LIGHT: Color32 = Color32::from_gray(204);
+const CHECKER_DARK: Color32 = Color32::from_gray(153);
+
+// 颜色块，半透明的颜色画在棋盘格上
+pub fn show_swatch(
+    ui: &mut Ui,
+    texture: &mut Option<TextureHandle>,
+    color: Color32,
+    size: Vec2,
+    rounding: CornerRadius,
+    stroke: Stroke,
+) -> Response {
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+
+    if color.a() < 255 {
+        let tex = texture.get_or_insert_with(|| {
+            ui.ctx().load_texture(
+                "checker_texture",
+                ColorImage::new(
+                    [2, 2],
+                    vec![CHECKER_LIGHT, CHECKER_DARK, CHECKER_DARK, CHECKER_LIGHT],
+                ),
+                TextureOptions::NEAREST_REPEAT,
+            )
+        });
+        let uv = Rect::from_min_max(
+            Pos2::ZERO,
+            Pos2::new(size.x / CHECKER_CELL / 2.0, size.y / CHECKER_CELL / 2.0),
+        );
+        ui.painter()
+            .add(RectShape::filled(rect, rounding, Color32::WHITE).with_texture(tex.id(), uv));
+    }
+
+    ui.painter()
+        .rect(rect, rounding, color, stroke, egui::StrokeKind::Middle);
+
+    response
+}
@@ -0,0 +1,47 @@
+use std::collections::BTreeMap;
+
+use egui::Ui;
+
+use crate::model::{
+    color::Color,
+    readout::Readout,
+    template::{Template, TemplatePreset},
+};
+
+// 右键菜单里为每个读数行选择输出模板，悬停显示模板和当前颜色的效果
+pub fn show_template_menu(
+    ui: &mut Ui,
+    rows: &[Readout],
+    presets: &[TemplatePreset],
+    templates: &mut BTreeMap<Readout, Template>,
+    color: &Color,
+) {
+    ui.menu_button("templates", |ui| {
+        for readout in rows {
+            ui.menu_button(readout.name(), |ui| {
+                let current = templates.get(readout).cloned();
+                if ui
+                    .radio(current.is_none(), "default")
+                    .on_hover_text(readout.format(color))
+                    .clicked()
+                {
+                    templates.remove(readout);
+                }
+                for preset in presets {
+                    let selected = current.as_ref() == Some(&preset.template);
+                    if ui
+                        .radio(selected, preset.name.as_str())
+                        .on_hover_text(format!(
+                            "{}\n{}",
+                            preset.template,
+                            preset.template.render(color)
+                        ))
+                        .clicked()
+                    {
+                        templates.insert(*readout, preset.template.clone());
+                    }
+                }
+            });
+        }
+    });
+}
@@ -4,7 +4,7 @@ use egui::{
 };
 
 use crate::model::{
-    color::{Color, HSL},
+    color::{Color, HSL, HSV},
     wheel_mode::WheelMode,
 };
 
@@ -13,16 +13,13 @@ fn generate_wheel_texture(
     outer_radius: f32, // 外圈半径（像素）
     ring_thickness: f32,
 ) -> Vec<Color32> {
-    // let hsva: Hsva = color.into();
     let mut pixels = vec![Color32::TRANSPARENT; tex_size * tex_size];
 
     let out_radius_mapped = tex_size as f32 / 2.0;
     let ring_thickness_mapped = out_radius_mapped / outer_radius * ring_thickness;
-    // let square_size_mapped = (out_radius_mapped - ring_thickness_mapped) * 2.0 / 1.414 - 3.0;
 
     let center = (tex_size as f32) / 2.0;
     let ring_inner = out_radius_mapped - ring_thickness_mapped;
-    // let square_half = square_size_mapped / 2.0;
 
     let samples = 4; // 每个像素 4×4 采样
 
@@ -49,11 +46,6 @@ fn generate_wheel_texture(
                         let hue = (angle / std::f32::consts::TAU).rem_euclid(1.0);
                         let hue = (hue + 5.0 / 12.0).rem_euclid(1.0);
                         Hsva::new(hue, 1.0, 1.0, 1.0)
-                    // } else if dx.abs() <= square_half && dy.abs() <= square_half {
-                    //     // SV 方块
-                    //     let s = (dx + square_half) / square_size_mapped; // 0..1
-                    //     let v = 1.0 - (dy + square_half) / square_size_mapped; // 0..1
-                    //     Hsva::new(hsva.h, s, v, 1.0)
                     } else {
                         Hsva::new(0.0, 0.0, 0.0, 0.0) // 透明
                     };
@@ -174,8 +166,8 @@ pub fn show_wheel(
                         let bottom_left = square_rect.left_bottom();
                         let bottom_right = square_rect.right_bottom();
 
-                        let col_left = Color32::from(Hsva::new(hsv.h / 360.0, 0.0, 1.0, 1.0));
-                        let col_right = Color32::from(Hsva::new(hsv.h / 360.0, 1.0, 1.0, 1.0));
+                        let col_left = HSV::new(hsv.h, 0.0, 1.0).to_rgb().to_color32();
+                        let col_right = HSV::new(hsv.h, 1.0, 1.0).to_rgb().to_color32();
 
                         let idx = mesh.vertices.len() as u32;
                         mesh.colored_vertex(top_left, col_left);
@@ -0,0 +1,66 @@
+use colorose::model::color::Color;
+use proptest::prelude::*;
+
+// RGB 经过 HSL/HSV 转换再转回，每个通道误差不超过 1
+fn assert_close(actual: Color, expected: Color) -> Result<(), TestCaseError> {
+    let channels = |c: Color| [c.r, c.g, c.b, c.a];
+    for (a, e) in channels(actual).into_iter().zip(channels(expected)) {
+        prop_assert!(
+            a.abs_diff(e) <= 1,
+            "{:?} is not within 1 of {:?}",
+            actual,
+            expected
+        );
+    }
+    Ok(())
+}
+
+fn any_color() -> impl Strategy<Value = Color> {
+    any::<[u8; 4]>().prop_map(|[r, g, b, a]| Color::new_rgba(r, g, b, a))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(4096))]
+
+    #[test]
+    fn rgb_hsl_rgb(color in any_color()) {
+        assert_close(color.to_hsl().to_rgb(), color)?;
+    }
+
+    #[test]
+    fn rgb_hsv_rgb(color in any_color()) {
+        assert_close(color.to_hsv().to_rgb(), color)?;
+    }
+
+    #[test]
+    fn rgb_hsl_hsv_rgb(color in any_color()) {
+        assert_close(color.to_hsl().to_hsv().to_rgb(), color)?;
+    }
+
+    #[test]
+    fn rgb_hsv_hsl_rgb(color in any_color()) {
+        assert_close(color.to_hsv().to_hsl().to_rgb(), color)?;
+    }
+}
+
+// 灰色、纯色和边界值
+#[test]
+fn round_trips_edge_colors() {
+    for value in [0, 1, 127, 128, 254, 255] {
+        for color in [
+            Color::new(value, value, value),
+            Color::new(value, 0, 0),
+            Color::new(0, value, 255),
+            Color::new_rgba(255, value, 0, value),
+        ] {
+            for converted in [
+                color.to_hsl().to_rgb(),
+                color.to_hsv().to_rgb(),
+                color.to_hsl().to_hsv().to_rgb(),
+                color.to_hsv().to_hsl().to_rgb(),
+            ] {
+                assert_close(converted, color).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use colorose::model::{
+    color::Color,
+    palette::{Palette, PaletteError, PaletteFormat, Swatch},
+};
+
+// 手写的色板文件，都是 Red、Sky Blue、Gray 50 三个颜色
+const ASE: &[u8] = include_bytes!("fixtures/palettes/brand.ase");
+const ACO: &[u8] = include_bytes!("fixtures/palettes/brand.aco");
+const GPL: &[u8] = include_bytes!("fixtures/palettes/brand.gpl");
+const JASC: &[u8] = include_bytes!("fixtures/palettes/brand.pal");
+const HEX: &[u8] = include_bytes!("fixtures/palettes/brand.hex");
+
+fn colors() -> [(&'static str, Color); 3] {
+    [
+        ("Red", Color::new(255, 0, 0)),
+        ("Sky Blue", Color::new(135, 206, 235)),
+        ("Gray 50", Color::new(128, 128, 128)),
+    ]
+}
+
+fn expected(name: &str, named_swatches: bool) -> Palette {
+    Palette {
+        name: name.to_string(),
+        swatches: colors()
+            .into_iter()
+            .map(|(swatch, color)| Swatch::new(if named_swatches { swatch } else { "" }, color))
+            .collect(),
+    }
+}
+
+fn fixtures() -> [(PaletteFormat, &'static [u8], Palette); 5] {
+    [
+        (PaletteFormat::ASE, ASE, expected("brand", true)),
+        (PaletteFormat::ACO, ACO, expected("brand", true)),
+        (PaletteFormat::GPL, GPL, expected("Brand", true)),
+        (PaletteFormat::JASC, JASC, expected("brand", false)),
+        (PaletteFormat::Hex, HEX, expected("brand", false)),
+    ]
+}
+
+#[test]
+fn reads_fixtures() {
+    for (format, bytes, palette) in fixtures() {
+        assert_eq!(
+            format.read("brand", bytes).unwrap(),
+            palette,
+            "{:?}",
+            format
+        );
+    }
+}
+
+#[test]
+fn round_trips_fixtures() {
+    for (format, bytes, palette) in fixtures() {
+        let written = format.write(&format.read("brand", bytes).unwrap());
+        assert_eq!(
+            format.read("brand", &written).unwrap(),
+            palette,
+            "{:?}",
+            format
+        );
+    }
+}
+
+// 文本格式写出的内容与手写的文件相同
+#[test]
+fn writes_text_fixtures_byte_for_byte() {
+    let gpl = String::from_utf8(PaletteFormat::GPL.write(&expected("Brand", true))).unwrap();
+    assert_eq!(
+        gpl,
+        "GIMP Palette\nName: Brand\n#\n255   0   0\tRed\n135 206 235\tSky Blue\n128 128 128\tGray 50\n"
+    );
+    assert_eq!(PaletteFormat::JASC.write(&expected("brand", false)), JASC);
+    assert_eq!(PaletteFormat::Hex.write(&expected("brand", false)), HEX);
+}
+
+#[test]
+fn rejects_malformed_binary_files() {
+    let read = |format: PaletteFormat, bytes: &[u8]| format.read("broken", bytes);
+
+    assert!(matches!(
+        read(PaletteFormat::ASE, b""),
+        Err(PaletteError::InvalidHeader(_))
+    ));
+    assert!(matches!(
+        read(PaletteFormat::ASE, b"ASEX\0\x01\0\0"),
+        Err(PaletteError::InvalidHeader(_))
+    ));
+    // 截断在块中间
+    for len in [8, 12, 20, ASE.len() - 1] {
+        assert!(
+            matches!(
+                read(PaletteFormat::ASE, &ASE[..len]),
+                Err(PaletteError::UnexpectedEnd)
+            ),
+            "ASE cut at {}",
+            len
+        );
+    }
+    let mut unknown_model = ASE.to_vec();
+    let at = unknown_model.windows(4).position(|w| w == b"Gray").unwrap();
+    unknown_model[at..at + 4].copy_from_slice(b"XYZW");
+    assert!(matches!(
+        read(PaletteFormat::ASE, &unknown_model),
+        Err(PaletteError::UnsupportedColorSpace(model)) if model == "XYZW"
+    ));
+
+    assert!(matches!(
+        read(PaletteFormat::ACO, b""),
+        Err(PaletteError::InvalidHeader(_))
+    ));
+    assert!(matches!(
+        read(PaletteFormat::ACO, b"\0\x03\0\0"),
+        Err(PaletteError::InvalidHeader(_))
+    ));
+    for len in [3, 10, ACO.len() - 1] {
+        assert!(
+            matches!(
+                read(PaletteFormat::ACO, &ACO[..len]),
+                Err(PaletteError::UnexpectedEnd)
+            ),
+            "ACO cut at {}",
+            len
+        );
+    }
+    // 版本 1 中的颜色空间 9 不存在
+    let mut unknown_space = ACO.to_vec();
+    unknown_space[5] = 9;
+    assert!(matches!(
+        read(PaletteFormat::ACO, &unknown_space),
+        Err(PaletteError::UnsupportedColorSpace(_))
+    ));
+}
+
+#[test]
+fn rejects_malformed_text_files() {
+    let read = |format: PaletteFormat, text: &str| format.read("broken", text.as_bytes());
+
+    assert!(matches!(
+        read(PaletteFormat::GPL, ""),
+        Err(PaletteError::InvalidHeader(_))
+    ));
+    assert!(matches!(
+        read(PaletteFormat::GPL, "Name: x\n1 2 3\n"),
+        Err(PaletteError::InvalidHeader(_))
+    ));
+    assert!(matches!(
+        read(
+            PaletteFormat::GPL,
+            "GIMP Palette\n255 0 0 Red\n255 0 Green\n"
+        ),
+        Err(PaletteError::InvalidLine(3))
+    ));
+    assert!(matches!(
+        read(PaletteFormat::GPL, "GIMP Palette\n256 0 0 Red\n"),
+        Err(PaletteError::InvalidLine(2))
+    ));
+
+    assert!(matches!(
+        read(PaletteFormat::JASC, "JASC\r\n"),
+        Err(PaletteError::InvalidHeader(_))
+    ));
+    assert!(matches!(
+        read(PaletteFormat::JASC, "JASC-PAL\r\n0100\r\n"),
+        Err(PaletteError::UnexpectedEnd)
+    ));
+    assert!(matches!(
+        read(PaletteFormat::JASC, "JASC-PAL\r\n0100\r\nmany\r\n"),
+        Err(PaletteError::InvalidLine(3))
+    ));
+    assert!(matches!(
+        read(PaletteFormat::JASC, "JASC-PAL\r\n0100\r\n3\r\n255 0 0\r\n"),
+        Err(PaletteError::UnexpectedEnd)
+    ));
+    assert!(matches!(
+        read(
+            PaletteFormat::JASC,
+            "JASC-PAL\r\n0100\r\n2\r\n255 0 0\r\n1 2\r\n"
+        ),
+        Err(PaletteError::InvalidLine(5))
+    ));
+
+    assert!(matches!(
+        read(PaletteFormat::Hex, "ff0000\nnot a color\n"),
+        Err(PaletteError::InvalidLine(2))
+    ));
+}